@@ -0,0 +1,121 @@
+// Everything the frontend account page needs in one response. Each source is
+// queried independently; failures become warnings instead of failing the request.
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use parity_scale_codec::Encode;
+use serde::Serialize;
+
+use crate::{
+    asset_symbol,
+    chain::{ChainError, ContractDepositInfo, ContractVestingSchedule},
+    error::{ApiError, ApiResult},
+    indexer::ClaimRecord,
+    ss58,
+    xcm_status::PendingClaim,
+    AppState, AssetBreakdown,
+};
+
+const RECENT_CLAIMS: usize = 20;
+
+#[derive(Debug, Serialize)]
+pub struct AccountSummary {
+    address: String,
+    locked: Option<String>,
+    claimable_now: Option<String>,
+    next_unlock: Option<u64>,
+    assets: Vec<AssetBreakdown>,
+    recent_claims: Vec<ClaimRecord>,
+    // Claims whose XCM delivery hasn't been confirmed yet
+    pending_claims: Vec<PendingClaim>,
+    warnings: Vec<String>,
+}
+
+fn or_warn<T>(warnings: &mut Vec<String>, source: &str, result: Result<T, ChainError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warnings.push(format!("{}: {}", source, err));
+            None
+        }
+    }
+}
+
+pub async fn get_account_summary(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<ResponseJson<AccountSummary>> {
+    let account_id = ss58::decode_for_network(&address, state.ss58_prefix)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid address: {}", err)))?;
+
+    let mut summary = AccountSummary {
+        address,
+        locked: None,
+        claimable_now: None,
+        next_unlock: None,
+        assets: Vec::new(),
+        recent_claims: state.activity.recent_claims_for(&account_id, RECENT_CLAIMS),
+        pending_claims: state.xcm.pending_for(&account_id),
+        warnings: Vec::new(),
+    };
+
+    let Some(chain) = state.chain.as_ref() else {
+        summary.warnings.push("Chain client not configured".to_string());
+        return Ok(ResponseJson(summary));
+    };
+
+    let args = account_id.encode();
+    let warnings = &mut summary.warnings;
+    let locked = or_warn(warnings, "total_locked_for", chain.query::<u128>("total_locked_for", &args).await);
+    let claimable = or_warn(warnings, "total_claimable", chain.query::<u128>("total_claimable", &args).await);
+    let deposit = or_warn(
+        warnings,
+        "get_deposit_info",
+        chain.query::<Option<ContractDepositInfo>>("get_deposit_info", &args).await,
+    );
+    let schedule = or_warn(
+        warnings,
+        "get_schedule",
+        chain.query::<Option<ContractVestingSchedule>>("get_schedule", &args).await,
+    );
+
+    summary.locked = locked.map(|locked| locked.to_string());
+    summary.claimable_now = claimable.map(|claimable| claimable.to_string());
+
+    // (unlock, asset, amount) for every open position
+    let mut positions = Vec::new();
+    if let Some(Some(info)) = deposit {
+        positions.push((info.unlock_timestamp, info.asset_id, info.amount + info.reserved_fee));
+    }
+    if let Some(Some(schedule)) = schedule {
+        for tranche in schedule.tranches {
+            positions.push((tranche.unlock_timestamp, schedule.asset_id, tranche.amount + tranche.reserved_fee));
+        }
+    }
+
+    // Contract timestamps are in milliseconds
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    summary.next_unlock = positions
+        .iter()
+        .map(|(unlock, _, _)| *unlock)
+        .filter(|unlock| *unlock > now)
+        .min();
+
+    let mut by_asset: BTreeMap<u32, u128> = BTreeMap::new();
+    for (_, asset_id, amount) in positions {
+        *by_asset.entry(asset_id).or_default() += amount;
+    }
+    summary.assets = by_asset
+        .into_iter()
+        .map(|(asset_id, locked)| AssetBreakdown {
+            asset_id,
+            symbol: asset_symbol(asset_id).to_string(),
+            locked: locked.to_string(),
+        })
+        .collect();
+
+    Ok(ResponseJson(summary))
+}
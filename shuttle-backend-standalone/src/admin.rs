@@ -0,0 +1,170 @@
+// Operator circuit breaker. Requests must carry `Authorization: Bearer <ADMIN_API_KEY>`;
+// the contract then checks that the backend signer is its admin (or, for emergency
+// approvals, one of its guardians).
+use axum::{
+    extract::{Json, State},
+    http::{header::AUTHORIZATION, HeaderMap},
+    response::Json as ResponseJson,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    chain::{ChainClient, ContractConfigHead, ERR_NOT_GUARDIAN, ERR_UNAUTHORIZED_ACCESS},
+    error::{ApiError, ApiResult},
+    require_chain, AppState,
+};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PauseRequest {
+    // Also pause (or unpause) claims, not just deposits
+    #[serde(default)]
+    claims: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PauseResponse {
+    deposits_paused: bool,
+    claims_paused: bool,
+    extrinsic_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmergencyApprovalResponse {
+    approvals: u32,
+    threshold: u32,
+    // emergency_unlock can now be called by the admin
+    threshold_reached: bool,
+    extrinsic_hash: String,
+}
+
+// Compares without short-circuiting so response timing doesn't leak the key
+fn keys_match(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len() && given.iter().zip(expected).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+pub(crate) fn require_admin_key(state: &AppState, headers: &HeaderMap) -> ApiResult<()> {
+    let expected = state
+        .admin_api_key
+        .as_deref()
+        .ok_or_else(|| ApiError::Unavailable("Admin API not configured".to_string()))?;
+    let given = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| ApiError::Unauthorized("Missing bearer token".to_string()))?;
+    if !keys_match(given.as_bytes(), expected.as_bytes()) {
+        return Err(ApiError::Unauthorized("Invalid bearer token".to_string()));
+    }
+    Ok(())
+}
+
+// Dry-runs the messages first so an unauthorized signer gets a 403 instead of a
+// failed extrinsic, then submits them together. `forbidden` is the contract error
+// that means the signer lacks the role. Returns the extrinsic hash.
+async fn submit_calls(chain: &ChainClient, messages: &[&str], forbidden: u8) -> ApiResult<String> {
+    for message in messages {
+        match chain.query::<Result<(), u8>>(message, &[]).await? {
+            Ok(()) => {}
+            Err(code) if code == forbidden => {
+                return Err(ApiError::Forbidden(format!(
+                    "Backend signer is not authorized to call {}",
+                    message
+                )))
+            }
+            Err(code) => {
+                return Err(ApiError::Upstream(format!("{} failed with contract error {}", message, code)))
+            }
+        }
+    }
+
+    let calls = messages
+        .iter()
+        .map(|message| ChainClient::message_data(message, &[]))
+        .collect();
+    let outcome = chain.submit_batch(calls, true).await?;
+    if let Some(failed) = outcome.results.iter().find(|result| !result.success) {
+        return Err(ApiError::Upstream(
+            failed.error.clone().unwrap_or_else(|| "Call failed".to_string()),
+        ));
+    }
+    Ok(outcome.extrinsic_hash)
+}
+
+async fn submit_admin_calls(chain: &ChainClient, messages: &[&str]) -> ApiResult<PauseResponse> {
+    let extrinsic_hash = submit_calls(chain, messages, ERR_UNAUTHORIZED_ACCESS).await?;
+    let config: ContractConfigHead = chain.query("get_config", &[]).await?;
+    Ok(PauseResponse {
+        deposits_paused: config.deposits_paused,
+        claims_paused: config.claims_paused,
+        extrinsic_hash,
+    })
+}
+
+pub async fn pause(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request: Option<Json<PauseRequest>>,
+) -> ApiResult<ResponseJson<PauseResponse>> {
+    require_admin_key(&state, &headers)?;
+    let chain = require_chain(&state)?;
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+
+    let mut messages = vec!["pause_deposits"];
+    if request.claims {
+        messages.push("pause_claims");
+    }
+    info!("Admin pause requested: {:?}", messages);
+    Ok(ResponseJson(submit_admin_calls(chain, &messages).await?))
+}
+
+pub async fn unpause(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request: Option<Json<PauseRequest>>,
+) -> ApiResult<ResponseJson<PauseResponse>> {
+    require_admin_key(&state, &headers)?;
+    let chain = require_chain(&state)?;
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+
+    let mut messages = vec!["unpause_deposits"];
+    if request.claims {
+        messages.push("unpause_claims");
+    }
+    info!("Admin unpause requested: {:?}", messages);
+    Ok(ResponseJson(submit_admin_calls(chain, &messages).await?))
+}
+
+// Records the backend signer's guardian approval for the next emergency_unlock
+pub async fn approve_emergency(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<ResponseJson<EmergencyApprovalResponse>> {
+    require_admin_key(&state, &headers)?;
+    let chain = require_chain(&state)?;
+
+    info!("Guardian emergency approval requested");
+    let extrinsic_hash = submit_calls(chain, &["approve_emergency"], ERR_NOT_GUARDIAN).await?;
+
+    let approvals: Vec<[u8; 32]> = chain.query("get_emergency_approvals", &[]).await?;
+    let (_, threshold): (Vec<[u8; 32]>, u32) = chain.query("get_guardians", &[]).await?;
+    let approvals = approvals.len() as u32;
+    Ok(ResponseJson(EmergencyApprovalResponse {
+        approvals,
+        threshold,
+        threshold_reached: approvals >= threshold,
+        extrinsic_hash,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_comparison() {
+        assert!(keys_match(b"s3cret", b"s3cret"));
+        assert!(!keys_match(b"s3cres", b"s3cret"));
+        assert!(!keys_match(b"s3cret-longer", b"s3cret"));
+    }
+}
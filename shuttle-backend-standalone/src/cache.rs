@@ -0,0 +1,134 @@
+// Short-lived cache for chain reads. Entries expire after a TTL, but the indexer
+// clears them as soon as a vault event lands so claims show up immediately.
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc;
+
+use crate::{
+    chain::{ContractClaimPreview, ContractDepositInfo},
+    AssetBreakdown,
+};
+
+// Chain reads are cached briefly so dashboards don't hammer the node
+pub const VAULT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+pub type CacheSlot<T> = RwLock<Option<(Instant, T)>>;
+
+// Per-account entries; a cached None records that the account has nothing on chain
+type AccountSlot<T> = RwLock<HashMap<[u8; 32], (Instant, Option<T>)>>;
+
+#[derive(Default)]
+pub struct VaultCache {
+    pub total_locked: CacheSlot<u128>,
+    pub breakdown: CacheSlot<Vec<AssetBreakdown>>,
+    deposits: AccountSlot<ContractDepositInfo>,
+    claim_previews: AccountSlot<ContractClaimPreview>,
+}
+
+pub fn cache_get<T: Clone>(slot: &CacheSlot<T>) -> Option<T> {
+    let entry = slot.read().unwrap();
+    match entry.as_ref() {
+        Some((stored_at, value)) if stored_at.elapsed() < VAULT_CACHE_TTL => Some(value.clone()),
+        _ => None,
+    }
+}
+
+pub fn cache_put<T>(slot: &CacheSlot<T>, value: T) {
+    *slot.write().unwrap() = Some((Instant::now(), value));
+}
+
+// Outer None is a cache miss
+fn account_get<T: Clone>(slot: &AccountSlot<T>, account_id: &[u8; 32]) -> Option<Option<T>> {
+    match slot.read().unwrap().get(account_id) {
+        Some((stored_at, value)) if stored_at.elapsed() < VAULT_CACHE_TTL => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn account_put<T>(slot: &AccountSlot<T>, account_id: [u8; 32], value: Option<T>) {
+    slot.write().unwrap().insert(account_id, (Instant::now(), value));
+}
+
+// Tells the cache that vault state changed, optionally for one account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invalidation {
+    pub account: Option<[u8; 32]>,
+}
+
+impl VaultCache {
+    pub fn get_deposit(&self, account_id: &[u8; 32]) -> Option<Option<ContractDepositInfo>> {
+        account_get(&self.deposits, account_id)
+    }
+
+    pub fn put_deposit(&self, account_id: [u8; 32], info: Option<ContractDepositInfo>) {
+        account_put(&self.deposits, account_id, info);
+    }
+
+    pub fn get_claim_preview(&self, account_id: &[u8; 32]) -> Option<Option<ContractClaimPreview>> {
+        account_get(&self.claim_previews, account_id)
+    }
+
+    pub fn put_claim_preview(&self, account_id: [u8; 32], preview: Option<ContractClaimPreview>) {
+        account_put(&self.claim_previews, account_id, preview);
+    }
+
+    // Any deposit or claim moves the vault totals, so aggregates are always dropped
+    pub fn invalidate(&self, invalidation: &Invalidation) {
+        *self.total_locked.write().unwrap() = None;
+        *self.breakdown.write().unwrap() = None;
+        if let Some(account_id) = invalidation.account {
+            self.deposits.write().unwrap().remove(&account_id);
+            self.claim_previews.write().unwrap().remove(&account_id);
+        }
+    }
+}
+
+// Applies invalidations published by the indexer until every sender is gone
+pub async fn run_invalidator(cache: Arc<VaultCache>, mut rx: mpsc::UnboundedReceiver<Invalidation>) {
+    while let Some(invalidation) = rx.recv().await {
+        cache.invalidate(&invalidation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit() -> ContractDepositInfo {
+        ContractDepositInfo {
+            amount: 1_000,
+            unlock_timestamp: 0,
+            asset_id: 1,
+            destination_parachain: 2000,
+            reserved_fee: 0,
+            destination_account: [0; 32],
+        }
+    }
+
+    #[tokio::test]
+    async fn claim_event_invalidates_cached_entries() {
+        let cache = Arc::new(VaultCache::default());
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        cache_put(&cache.total_locked, 1_000);
+        cache_put(&cache.breakdown, Vec::new());
+        cache.put_deposit(alice, Some(deposit()));
+        cache.put_deposit(bob, Some(deposit()));
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let invalidator = tokio::spawn(run_invalidator(cache.clone(), rx));
+        tx.send(Invalidation { account: Some(alice) }).unwrap();
+        drop(tx);
+        invalidator.await.unwrap();
+
+        assert_eq!(cache_get(&cache.total_locked), None);
+        assert!(cache_get(&cache.breakdown).is_none());
+        assert!(cache.get_deposit(&alice).is_none());
+        // Other accounts keep their cached deposit
+        assert!(matches!(cache.get_deposit(&bob), Some(Some(_))));
+    }
+}
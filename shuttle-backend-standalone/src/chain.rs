@@ -0,0 +1,510 @@
+// Chain access for the vesting vault contract via subxt
+use std::{fmt, str::FromStr};
+
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use parity_scale_codec::{Compact, Decode, Encode, Input};
+use subxt::{
+    config::polkadot::PolkadotExtrinsicParamsBuilder,
+    dynamic::Value,
+    utils::{AccountId32, H256},
+    OnlineClient, PolkadotConfig,
+};
+use subxt_signer::{sr25519::Keypair, SecretUri};
+use tokio::sync::mpsc;
+
+use crate::{
+    nonce::NonceTracker,
+    pool::{PoolSaturated, PoolStats, RpcPool, QUEUE_TIMEOUT},
+};
+
+// Gas budget reserved for a single `claim_cross_chain` call
+pub const CLAIM_GAS_REF_TIME: u64 = 5_000_000_000;
+pub const CLAIM_GAS_PROOF_SIZE: u64 = 256 * 1024;
+
+// Keep a whole batch well under the normal-dispatch share of a 2s block
+pub const MAX_BATCH_REF_TIME: u64 = 500_000_000_000;
+pub const MAX_BATCH_PROOF_SIZE: u64 = 4 * 1024 * 1024;
+
+// Each contract's storage is a default child trie whose root the main trie keeps under this prefix
+const CHILD_STORAGE_PREFIX: &[u8] = b":child_storage:default:";
+
+#[derive(Debug)]
+pub enum ChainError {
+    Config(String),
+    BatchTooLarge { calls: usize, max: usize },
+    ContractReverted(String),
+    Decode(String),
+    // Every RPC slot stayed busy for the whole queue timeout
+    Saturated { max: usize },
+    Subxt(subxt::Error),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::Config(msg) => write!(f, "chain config error: {}", msg),
+            ChainError::BatchTooLarge { calls, max } => {
+                write!(f, "batch of {} calls exceeds the weight limit ({} max)", calls, max)
+            }
+            ChainError::ContractReverted(msg) => write!(f, "contract call reverted: {}", msg),
+            ChainError::Decode(msg) => write!(f, "failed to decode contract result: {}", msg),
+            ChainError::Saturated { max } => write!(f, "all {} RPC slots are busy, try again shortly", max),
+            ChainError::Subxt(err) => write!(f, "chain error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+impl From<PoolSaturated> for ChainError {
+    fn from(err: PoolSaturated) -> Self {
+        ChainError::Saturated { max: err.max }
+    }
+}
+
+impl From<subxt::Error> for ChainError {
+    fn from(err: subxt::Error) -> Self {
+        ChainError::Subxt(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, serde::Serialize)]
+pub struct Weight {
+    #[codec(compact)]
+    pub ref_time: u64,
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+#[derive(Debug, Encode, Decode)]
+enum StorageDeposit {
+    Refund(Compact<u128>),
+    Charge(Compact<u128>),
+}
+
+#[derive(Debug, Decode)]
+struct ExecReturnValue {
+    flags: u32,
+    data: Vec<u8>,
+}
+
+const REVERT_FLAG: u32 = 1;
+
+// Leading fields of pallet-contracts' `ContractExecResult`. The dispatch error and
+// collected events that follow the result are not needed, so decoding stops there.
+#[derive(Debug)]
+struct ContractExecResult {
+    gas_consumed: Weight,
+    gas_required: Weight,
+    result: Result<ExecReturnValue, ()>,
+}
+
+impl Decode for ContractExecResult {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+        let gas_consumed = Weight::decode(input)?;
+        let gas_required = Weight::decode(input)?;
+        let _storage_deposit = StorageDeposit::decode(input)?;
+        let _debug_message = Vec::<u8>::decode(input)?;
+        let result = match u8::decode(input)? {
+            0 => Ok(ExecReturnValue::decode(input)?),
+            _ => Err(()),
+        };
+        Ok(Self {
+            gas_consumed,
+            gas_required,
+            result,
+        })
+    }
+}
+
+// Outcome of a contract call that was executed but not submitted
+#[derive(Debug, Clone)]
+pub struct DryRun {
+    pub gas_consumed: Weight,
+    pub gas_required: Weight,
+    // The contract set the revert flag; `data` then holds its error
+    pub reverted: bool,
+    // Raw SCALE output, still wrapped in ink!'s `Result<_, LangError>`
+    pub data: Vec<u8>,
+}
+
+// Mirror of the contract's `DepositInfo`; every field is needed to decode it
+#[allow(dead_code)]
+#[derive(Debug, Clone, Decode)]
+pub struct ContractDepositInfo {
+    pub amount: u128,
+    pub unlock_timestamp: u64,
+    pub asset_id: u32,
+    pub destination_parachain: u32,
+    pub reserved_fee: u128,
+    pub destination_account: [u8; 32],
+}
+
+// Mirror of the contract's `ClaimPreview`
+#[allow(dead_code)]
+#[derive(Debug, Clone, Decode)]
+pub struct ContractClaimPreview {
+    pub gross: u128,
+    pub fee: u128,
+    pub execution_fee: u128,
+    pub fee_credit_used: u128,
+    pub reward: u128,
+    pub net: u128,
+    pub destination_parachain: u32,
+    pub destination_account: [u8; 32],
+}
+
+// Indices of `VestingError` variants; contract errors decode as their variant index
+pub const ERR_NO_DEPOSIT_FOUND: u8 = 2;
+pub const ERR_UNAUTHORIZED_ACCESS: u8 = 3;
+pub const ERR_NOT_GUARDIAN: u8 = 35;
+
+// Leading fields of the contract's `VaultConfig`, up to `claims_paused`; the rest
+// of the struct is left undecoded
+#[allow(dead_code)]
+#[derive(Debug, Clone, Decode)]
+pub struct ContractConfigHead {
+    pub admin: [u8; 32],
+    pub treasury: [u8; 32],
+    pub min_lock: u64,
+    pub max_lock: u64,
+    pub claim_fee_bps: u16,
+    pub burn_bps: u16,
+    pub fee_buffer_bps: u16,
+    pub xcm_execution_fee: u128,
+    pub deposits_paused: bool,
+    pub emergency_mode: bool,
+    pub allowlist_enabled: bool,
+    pub reconciliation_tolerance: u128,
+    pub claims_paused: bool,
+}
+
+// Mirror of the contract's `VestingSchedule`
+#[allow(dead_code)]
+#[derive(Debug, Clone, Decode)]
+pub struct ContractVestingSchedule {
+    pub asset_id: u32,
+    pub destination_parachain: u32,
+    pub tranches: Vec<ContractTranche>,
+}
+
+#[derive(Debug, Clone, Decode)]
+pub struct ContractTranche {
+    pub amount: u128,
+    pub unlock_timestamp: u64,
+    pub reserved_fee: u128,
+}
+
+// Mirror of the contract's `XcmStatus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode)]
+pub enum ContractXcmStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+// An event emitted by the vault; with ink! 5 the first topic identifies the event type
+#[derive(Debug, Clone)]
+pub struct ContractEvent {
+    pub block_number: u64,
+    // Block time in milliseconds, from `Timestamp::Now`
+    pub block_timestamp: Option<u64>,
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchCallResult {
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchOutcome {
+    pub extrinsic_hash: String,
+    pub results: Vec<BatchCallResult>,
+}
+
+pub struct ChainClient {
+    api: OnlineClient<PolkadotConfig>,
+    signer: Keypair,
+    contract: AccountId32,
+    nonces: NonceTracker,
+    // Bounds queries and submissions; the long-lived subscriptions don't take a slot
+    pool: RpcPool,
+}
+
+impl ChainClient {
+    pub async fn connect(
+        rpc_url: &str,
+        contract_address: &str,
+        signer_uri: &str,
+        max_in_flight: usize,
+    ) -> Result<Self, ChainError> {
+        let contract = AccountId32::from_str(contract_address)
+            .map_err(|e| ChainError::Config(format!("invalid contract address: {:?}", e)))?;
+        let uri = SecretUri::from_str(signer_uri)
+            .map_err(|e| ChainError::Config(format!("invalid signer uri: {}", e)))?;
+        let signer = Keypair::from_uri(&uri)
+            .map_err(|e| ChainError::Config(format!("invalid signer key: {}", e)))?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(rpc_url).await?;
+
+        Ok(Self {
+            api,
+            signer,
+            contract,
+            nonces: NonceTracker::default(),
+            pool: RpcPool::new(max_in_flight, QUEUE_TIMEOUT),
+        })
+    }
+
+    pub fn pool_stats(&self) -> PoolStats {
+        self.pool.stats()
+    }
+
+    // ink! selectors are the first four bytes of BLAKE2b-256 of the message name
+    pub fn message_data(message: &str, encoded_args: &[u8]) -> Vec<u8> {
+        let hash = Blake2b::<U32>::digest(message.as_bytes());
+        let mut data = hash[..4].to_vec();
+        data.extend_from_slice(encoded_args);
+        data
+    }
+
+    // Executes a contract message via the `ContractsApi_call` runtime API without
+    // submitting it. `origin` defaults to the backend signer.
+    pub async fn dry_run(
+        &self,
+        message: &str,
+        encoded_args: &[u8],
+        origin: Option<AccountId32>,
+    ) -> Result<DryRun, ChainError> {
+        let _slot = self.pool.acquire().await?;
+        let origin = origin.unwrap_or_else(|| self.signer.public_key().to_account_id());
+        let params = (
+            origin,
+            self.contract.clone(),
+            0u128,
+            None::<Weight>,
+            None::<Compact<u128>>,
+            Self::message_data(message, encoded_args),
+        )
+            .encode();
+
+        let exec: ContractExecResult = self
+            .api
+            .runtime_api()
+            .at_latest()
+            .await?
+            .call_raw("ContractsApi_call", Some(&params))
+            .await?;
+
+        let ret = exec
+            .result
+            .map_err(|_| ChainError::ContractReverted(format!("{} failed to dispatch", message)))?;
+        Ok(DryRun {
+            gas_consumed: exec.gas_consumed,
+            gas_required: exec.gas_required,
+            reverted: ret.flags & REVERT_FLAG != 0,
+            data: ret.data,
+        })
+    }
+
+    // Dry-runs a read-only contract message and decodes its return value
+    pub async fn query<T: Decode>(&self, message: &str, encoded_args: &[u8]) -> Result<T, ChainError> {
+        let run = self.dry_run(message, encoded_args, None).await?;
+        if run.reverted {
+            return Err(ChainError::ContractReverted(format!("{} reverted", message)));
+        }
+
+        // ink! wraps every message return in `Result<T, LangError>`
+        let mut data = &run.data[..];
+        match u8::decode(&mut data).map_err(|e| ChainError::Decode(e.to_string()))? {
+            0 => T::decode(&mut data).map_err(|e| ChainError::Decode(e.to_string())),
+            _ => Err(ChainError::ContractReverted(format!("{} could not read input", message))),
+        }
+    }
+
+    // Follows finalized blocks and hands every event emitted by the vault contract
+    // to `on_event`. Returns when the subscription ends.
+    pub async fn watch_contract_events<F: FnMut(ContractEvent)>(&self, mut on_event: F) -> Result<(), ChainError> {
+        let mut blocks = self.api.blocks().subscribe_finalized().await?;
+        while let Some(block) = blocks.next().await {
+            let block = block?;
+            let block_number: u64 = block.number().into();
+            let events = block.events().await?;
+            let mut emitted = Vec::new();
+            for event in events.iter() {
+                let event = event?;
+                if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                    continue;
+                }
+                // ContractEmitted { contract: AccountId32, data: Vec<u8> }
+                let (contract, data) = <([u8; 32], Vec<u8>)>::decode(&mut event.field_bytes())
+                    .map_err(|e| ChainError::Decode(e.to_string()))?;
+                if contract == self.contract.0 {
+                    emitted.push((event.topics().iter().map(|topic| topic.0).collect(), data));
+                }
+            }
+            if emitted.is_empty() {
+                continue;
+            }
+
+            let block_timestamp = self.block_timestamp(block.hash()).await?;
+            for (topics, data) in emitted {
+                on_event(ContractEvent {
+                    block_number,
+                    block_timestamp,
+                    topics,
+                    data,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn block_timestamp(&self, block_hash: H256) -> Result<Option<u64>, ChainError> {
+        let address = subxt::dynamic::storage("Timestamp", "Now", Vec::<Value>::new());
+        let key = self.api.storage().address_bytes(&address)?;
+        let raw = self.api.storage().at(block_hash).fetch_raw(&key[..]).await?;
+        raw.map(|raw| u64::decode(&mut &raw[..]).map_err(|e| ChainError::Decode(e.to_string())))
+            .transpose()
+    }
+
+    async fn contract_trie_id(&self) -> Result<Vec<u8>, ChainError> {
+        let address = subxt::dynamic::storage(
+            "Contracts",
+            "ContractInfoOf",
+            vec![Value::from_bytes(self.contract.0)],
+        );
+        let key = self.api.storage().address_bytes(&address)?;
+        let raw = self
+            .api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch_raw(&key[..])
+            .await?
+            .ok_or_else(|| ChainError::Config("contract not found on chain".to_string()))?;
+        // `trie_id` is the first field of pallet-contracts' `ContractInfo`
+        Vec::<u8>::decode(&mut &raw[..]).map_err(|e| ChainError::Decode(e.to_string()))
+    }
+
+    // Signals `changed` on the first finalized block and whenever the contract's storage
+    // root moves afterwards. Returns when the block subscription ends.
+    pub async fn watch_storage_root(&self, changed: mpsc::UnboundedSender<()>) -> Result<(), ChainError> {
+        let mut root_key = CHILD_STORAGE_PREFIX.to_vec();
+        root_key.extend(self.contract_trie_id().await?);
+
+        let mut last_root = None;
+        let mut blocks = self.api.blocks().subscribe_finalized().await?;
+        while let Some(block) = blocks.next().await {
+            let root = self.api.storage().at(block?.hash()).fetch_raw(&root_key[..]).await?;
+            if last_root.as_ref() != Some(&root) {
+                last_root = Some(root);
+                let _ = changed.send(());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn max_batch_size() -> usize {
+        let by_ref_time = MAX_BATCH_REF_TIME / CLAIM_GAS_REF_TIME;
+        let by_proof_size = MAX_BATCH_PROOF_SIZE / CLAIM_GAS_PROOF_SIZE;
+        by_ref_time.min(by_proof_size) as usize
+    }
+
+    fn contract_call(&self, data: Vec<u8>) -> Value {
+        subxt::dynamic::tx(
+            "Contracts",
+            "call",
+            vec![
+                Value::unnamed_variant("Id", [Value::from_bytes(self.contract.0)]),
+                Value::u128(0),
+                Value::named_composite([
+                    ("ref_time", Value::u128(CLAIM_GAS_REF_TIME as u128)),
+                    ("proof_size", Value::u128(CLAIM_GAS_PROOF_SIZE as u128)),
+                ]),
+                Value::unnamed_variant("None", []),
+                Value::from_bytes(data),
+            ],
+        )
+        .into_value()
+    }
+
+    // Wraps several contract calls into one `Utility` extrinsic. Atomic batches use
+    // `batch_all` (all or nothing); otherwise `force_batch` reports every call separately.
+    pub async fn submit_batch(
+        &self,
+        calls: Vec<Vec<u8>>,
+        atomic: bool,
+    ) -> Result<BatchOutcome, ChainError> {
+        let max = Self::max_batch_size();
+        if calls.len() > max {
+            return Err(ChainError::BatchTooLarge { calls: calls.len(), max });
+        }
+
+        let _slot = self.pool.acquire().await?;
+        let count = calls.len();
+        let inner: Vec<Value> = calls.into_iter().map(|data| self.contract_call(data)).collect();
+        let call_name = if atomic { "batch_all" } else { "force_batch" };
+        let tx = subxt::dynamic::tx("Utility", call_name, vec![Value::unnamed_composite(inner)]);
+
+        let tx_client = self.api.tx();
+        let signer_id: AccountId32 = self.signer.public_key().to_account_id();
+        let nonce = self
+            .nonces
+            .reserve(|| tx_client.account_nonce(&signer_id))
+            .await?;
+        let params = PolkadotExtrinsicParamsBuilder::new().nonce(nonce).build();
+
+        let progress = match tx_client
+            .sign_and_submit_then_watch(&tx, &self.signer, params)
+            .await
+        {
+            Ok(progress) => progress,
+            Err(err) => {
+                // A rejected submission usually means the local nonce drifted from the chain
+                self.nonces.resync().await;
+                return Err(err.into());
+            }
+        };
+        let extrinsic_hash = format!("0x{}", hex::encode(progress.extrinsic_hash()));
+
+        let events = match progress.wait_for_finalized_success().await {
+            Ok(events) => events,
+            // A failed `batch_all` reverts every call in it
+            Err(subxt::Error::Runtime(err)) => {
+                let error = err.to_string();
+                let results = (0..count)
+                    .map(|_| BatchCallResult {
+                        success: false,
+                        error: Some(error.clone()),
+                    })
+                    .collect();
+                return Ok(BatchOutcome { extrinsic_hash, results });
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut results = Vec::with_capacity(count);
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() != "Utility" {
+                continue;
+            }
+            match event.variant_name() {
+                "ItemCompleted" => results.push(BatchCallResult {
+                    success: true,
+                    error: None,
+                }),
+                "ItemFailed" => results.push(BatchCallResult {
+                    success: false,
+                    error: Some(format!("{:?}", event.field_values()?)),
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(BatchOutcome { extrinsic_hash, results })
+    }
+}
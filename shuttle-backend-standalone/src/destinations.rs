@@ -0,0 +1,114 @@
+// Per-asset claim destination allowlist, checked before a claim reaches the chain.
+// Loaded from the file named by the CLAIM_DESTINATIONS_FILE secret and re-read
+// whenever the file changes, so routes can be edited without a redeploy.
+//
+// File format, one asset per line:
+//   # asset_id = parachain, parachain, ...
+//   1 = 1000, 2000
+//   2 = 1000
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use tracing::{info, warn};
+
+const RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DestinationAllowlist {
+    by_asset: HashMap<u32, HashSet<u32>>,
+}
+
+impl DestinationAllowlist {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut by_asset: HashMap<u32, HashSet<u32>> = HashMap::new();
+        for (index, line) in spec.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (asset, destinations) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `asset_id = parachain, ...`", index + 1))?;
+            let asset_id: u32 = asset
+                .trim()
+                .parse()
+                .map_err(|_| format!("line {}: invalid asset id `{}`", index + 1, asset.trim()))?;
+            let allowed = by_asset.entry(asset_id).or_default();
+            for destination in destinations.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+                let parachain: u32 = destination
+                    .parse()
+                    .map_err(|_| format!("line {}: invalid parachain id `{}`", index + 1, destination))?;
+                allowed.insert(parachain);
+            }
+        }
+        Ok(Self { by_asset })
+    }
+
+    // Assets without an entry are unrestricted; the contract still enforces its own list
+    pub fn restricts(&self, asset_id: u32) -> bool {
+        self.by_asset.contains_key(&asset_id)
+    }
+
+    pub fn allows(&self, asset_id: u32, parachain: u32) -> bool {
+        match self.by_asset.get(&asset_id) {
+            Some(allowed) => allowed.contains(&parachain),
+            None => true,
+        }
+    }
+}
+
+fn modified_at(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+// Loads the file now and then polls it for changes. A file that fails to parse
+// is logged and the previous allowlist stays in force.
+pub async fn run_reloader(path: PathBuf, allowlist: Arc<RwLock<DestinationAllowlist>>) {
+    let mut loaded_at = None;
+    let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+    loop {
+        interval.tick().await;
+        let modified = modified_at(&path);
+        if modified.is_none() || modified == loaded_at {
+            continue;
+        }
+        loaded_at = modified;
+
+        let parsed = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|spec| DestinationAllowlist::parse(&spec));
+        match parsed {
+            Ok(parsed) => {
+                info!("Loaded claim destinations for {} assets", parsed.by_asset.len());
+                *allowlist.write().unwrap() = parsed;
+            }
+            Err(err) => warn!("Ignoring invalid {}: {}", path.display(), err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_per_asset_destinations() {
+        let allowlist = DestinationAllowlist::parse("# DOT\n1 = 1000, 2000\n\n2=1000\n").unwrap();
+        assert!(allowlist.allows(1, 2000));
+        assert!(!allowlist.allows(2, 2000));
+        // Unlisted assets are left to the contract
+        assert!(!allowlist.restricts(3));
+        assert!(allowlist.allows(3, 2000));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(DestinationAllowlist::parse("1: 1000").is_err());
+        assert!(DestinationAllowlist::parse("1 = AssetHub").is_err());
+    }
+}
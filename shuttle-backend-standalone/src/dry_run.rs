@@ -0,0 +1,118 @@
+// Generic dry-run of a vault message for integrators. Only messages the deployed
+// metadata lists are accepted: any read-only one, plus the claim and deposit calls
+// users commonly want to simulate. Nothing is ever submitted.
+use axum::{
+    extract::{Json, State},
+    response::Json as ResponseJson,
+};
+use serde::{Deserialize, Serialize};
+use subxt::utils::AccountId32;
+
+use crate::{
+    chain::Weight,
+    error::{ApiError, ApiResult},
+    metadata::ContractMetadata,
+    require_chain, ss58, AppState,
+};
+
+// State-changing messages that may still be dry-run
+const MUTATING_ALLOWLIST: &[&str] = &["claim_cross_chain", "deposit_with_asset"];
+
+#[derive(Debug, Deserialize)]
+pub struct DryRunRequest {
+    message: String,
+    // SCALE-encoded arguments as 0x-prefixed hex; empty for messages without any
+    #[serde(default)]
+    args: String,
+    // SS58 caller to simulate, e.g. the depositor for `claim_cross_chain`;
+    // defaults to the backend signer
+    origin: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DryRunResponse {
+    message: String,
+    reverted: bool,
+    // Raw SCALE output as hex, wrapped in ink!'s `Result<_, LangError>`; decode it
+    // with the message's return type from /contract/metadata
+    result: String,
+    gas_consumed: Weight,
+    gas_required: Weight,
+}
+
+fn check_message(metadata: &ContractMetadata, message: &str) -> ApiResult<()> {
+    match metadata.message_mutates(message) {
+        None => Err(ApiError::BadRequest(format!("Unknown message: {}", message))),
+        Some(true) if !MUTATING_ALLOWLIST.contains(&message) => {
+            Err(ApiError::Forbidden(format!("{} can't be dry-run through this endpoint", message)))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+fn decode_args(args: &str) -> ApiResult<Vec<u8>> {
+    let hex_args = args.strip_prefix("0x").unwrap_or(args);
+    hex::decode(hex_args).map_err(|err| ApiError::BadRequest(format!("Invalid args hex: {}", err)))
+}
+
+pub async fn dry_run(
+    State(state): State<AppState>,
+    Json(request): Json<DryRunRequest>,
+) -> ApiResult<ResponseJson<DryRunResponse>> {
+    // Without the metadata there is nothing to validate the message name against
+    let metadata = state
+        .contract_metadata
+        .as_deref()
+        .ok_or_else(|| ApiError::Unavailable("Contract metadata not configured".to_string()))?;
+    check_message(metadata, &request.message)?;
+    let args = decode_args(&request.args)?;
+    let origin = request
+        .origin
+        .as_deref()
+        .map(|address| {
+            ss58::decode_for_network(address, state.ss58_prefix)
+                .map(AccountId32)
+                .map_err(|err| ApiError::BadRequest(format!("Invalid origin: {}", err)))
+        })
+        .transpose()?;
+
+    let chain = require_chain(&state)?;
+    let run = chain.dry_run(&request.message, &args, origin).await?;
+    Ok(ResponseJson(DryRunResponse {
+        message: request.message,
+        reverted: run.reverted,
+        result: format!("0x{}", hex::encode(run.data)),
+        gas_consumed: run.gas_consumed,
+        gas_required: run.gas_required,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_read_only_and_allowlisted_messages_pass() {
+        let path = std::env::temp_dir().join(format!("vault_dry_run_{}.contract", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"spec":{"messages":[
+                {"label":"get_total_locked","mutates":false},
+                {"label":"claim_cross_chain","mutates":true},
+                {"label":"emergency_unlock","mutates":true}
+            ]}}"#,
+        )
+        .unwrap();
+        let metadata = ContractMetadata::load(&path, None).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(check_message(&metadata, "get_total_locked").is_ok());
+        assert!(check_message(&metadata, "claim_cross_chain").is_ok());
+        assert!(matches!(check_message(&metadata, "emergency_unlock"), Err(ApiError::Forbidden(_))));
+        // Allowlisted, but not part of this deployment
+        assert!(matches!(check_message(&metadata, "deposit_with_asset"), Err(ApiError::BadRequest(_))));
+
+        assert_eq!(decode_args("0x0100").unwrap(), vec![1, 0]);
+        assert!(decode_args("0xzz").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+// Error type shared by the HTTP handlers. Every failure is returned as
+// `{ "error": <kind>, "message": <details> }` with a matching status code.
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson, Response},
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::chain::ChainError;
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    BadRequest(String),
+    // Missing or wrong API credentials
+    Unauthorized(String),
+    // The backend signer lacks the on-chain role for the call
+    Forbidden(String),
+    NotFound(String),
+    // Well-formed but refused by policy, e.g. a disallowed claim destination
+    Unprocessable(String),
+    // No chain client is configured (demo mode)
+    Unavailable(String),
+    // The node or the contract failed
+    Upstream(String),
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody<'a> {
+    error: &'a str,
+    message: &'a str,
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Upstream(_) => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Unprocessable(_) => "unprocessable",
+            ApiError::Unavailable(_) => "unavailable",
+            ApiError::Upstream(_) => "upstream",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Forbidden(message)
+            | ApiError::NotFound(message)
+            | ApiError::Unprocessable(message)
+            | ApiError::Unavailable(message)
+            | ApiError::Upstream(message) => message,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            error: self.kind(),
+            message: self.message(),
+        };
+        (self.status(), ResponseJson(body)).into_response()
+    }
+}
+
+impl From<ChainError> for ApiError {
+    fn from(err: ChainError) -> Self {
+        match err {
+            ChainError::BatchTooLarge { .. } => ApiError::BadRequest(err.to_string()),
+            ChainError::Saturated { .. } => ApiError::Unavailable(err.to_string()),
+            _ => {
+                warn!("Chain request failed: {}", err);
+                ApiError::Upstream(err.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_errors_map_to_status_codes() {
+        let too_large = ApiError::from(ChainError::BatchTooLarge { calls: 80, max: 64 });
+        assert_eq!(too_large.status(), StatusCode::BAD_REQUEST);
+
+        let reverted = ApiError::from(ChainError::ContractReverted("get_total_locked reverted".to_string()));
+        assert_eq!(reverted.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(reverted.kind(), "upstream");
+
+        let saturated = ApiError::from(ChainError::Saturated { max: 16 });
+        assert_eq!(saturated.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn response_carries_status() {
+        let response = ApiError::NotFound("Account has no deposit".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
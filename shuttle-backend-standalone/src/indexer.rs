@@ -0,0 +1,377 @@
+// Watches finalized blocks for vault events, publishes cache invalidations,
+// keeps a bounded log of recent claims and hands new claims to the XCM tracker
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use parity_scale_codec::Decode;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+
+use crate::{
+    cache::Invalidation,
+    chain::{ChainClient, ContractEvent},
+    ws::StreamEvent,
+    xcm_status::XcmTracker,
+    AppState,
+};
+
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+// Claims kept in memory across all accounts; older ones are dropped
+const ACTIVITY_CAPACITY: usize = 1_000;
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const DEFAULT_FEED_LIMIT: usize = 50;
+const MAX_FEED_LIMIT: usize = 200;
+
+// Must match the field types of the contract's `ClaimInitiated` event
+const CLAIM_INITIATED_SIGNATURE: &str =
+    "ClaimInitiated(AccountId,Balance,u32,[u8;32],Balance,Balance,u64,AssetId,[u8;32],u32,[u8;32])";
+
+// Per-user events forwarded by name on /ws/events; all lead with the user's AccountId
+const USER_EVENT_SIGNATURES: &[(&str, &str)] = &[
+    ("Deposited", "Deposited(AccountId,Balance,AssetId,Timestamp,Balance,Balance,Balance)"),
+    ("ClaimInitiated", CLAIM_INITIATED_SIGNATURE),
+    ("Granted", "Granted(AccountId,AccountId,Balance,AssetId,Timestamp)"),
+    ("EarlyWithdrawn", "EarlyWithdrawn(AccountId,u16,Balance)"),
+    ("RewardsClaimed", "RewardsClaimed(AccountId,Balance,AssetId)"),
+];
+
+#[derive(Debug, Decode)]
+struct ClaimInitiated {
+    user: [u8; 32],
+    amount: u128,
+    destination_parachain: u32,
+    xcm_hash: [u8; 32],
+    _fee_to_treasury: u128,
+    _fee_burned: u128,
+    receipt_id: u64,
+    asset_id: u32,
+    _destination_account: [u8; 32],
+    _stored_destination_parachain: u32,
+    _stored_destination_account: [u8; 32],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClaimRecord {
+    #[serde(skip)]
+    pub account: [u8; 32],
+    pub block_number: u64,
+    // Milliseconds, from the including block
+    pub timestamp: Option<u64>,
+    pub amount: String,
+    pub asset_id: u32,
+    pub destination_parachain: u32,
+    pub xcm_hash: String,
+    // Key of the contract's `get_claim_receipt`
+    pub receipt_id: u64,
+}
+
+// A claim with its position in the log, which doubles as the feed cursor
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityEntry {
+    pub id: u64,
+    #[serde(flatten)]
+    pub claim: ClaimRecord,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityPage {
+    pub items: Vec<ActivityEntry>,
+    // Pass as `cursor` to fetch the next (older) page; None on the last page
+    pub next_cursor: Option<u64>,
+}
+
+// What pruning removed from the log, per asset, so totals survive retention
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ArchivedTotals {
+    pub claims: u64,
+    pub amount: u128,
+}
+
+#[derive(Default)]
+pub struct ActivityLog {
+    claims: RwLock<VecDeque<ActivityEntry>>,
+    archived: RwLock<BTreeMap<u32, ArchivedTotals>>,
+}
+
+impl ActivityLog {
+    pub fn record_claim(&self, record: ClaimRecord) {
+        let mut claims = self.claims.write().unwrap();
+        let id = claims.back().map_or(0, |last| last.id + 1);
+        claims.push_back(ActivityEntry { id, claim: record });
+        if claims.len() > ACTIVITY_CAPACITY {
+            claims.pop_front();
+        }
+    }
+
+    // Newest first
+    pub fn recent_claims_for(&self, account_id: &[u8; 32], limit: usize) -> Vec<ClaimRecord> {
+        self.claims
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|entry| entry.claim.account == *account_id)
+            .take(limit)
+            .map(|entry| entry.claim.clone())
+            .collect()
+    }
+
+    // Newest first, starting below `cursor` when given
+    pub fn page(&self, cursor: Option<u64>, limit: usize) -> ActivityPage {
+        let claims = self.claims.read().unwrap();
+        let mut older = claims
+            .iter()
+            .rev()
+            .filter(|entry| cursor.is_none_or(|cursor| entry.id < cursor));
+        let items: Vec<ActivityEntry> = older.by_ref().take(limit).cloned().collect();
+        let next_cursor = match older.next() {
+            Some(_) => items.last().map(|entry| entry.id),
+            None => None,
+        };
+        ActivityPage { items, next_cursor }
+    }
+
+    // Drops claims from blocks older than `cutoff_ms` and folds them into the archived
+    // totals. Both change under the log's write lock, so readers never see one without
+    // the other. Entries without a block time are left to the capacity bound.
+    pub fn prune_older_than(&self, cutoff_ms: u64) -> usize {
+        let mut claims = self.claims.write().unwrap();
+        let mut archived = self.archived.write().unwrap();
+        let before = claims.len();
+        claims.retain(|entry| match entry.claim.timestamp {
+            Some(timestamp) if timestamp < cutoff_ms => {
+                let totals = archived.entry(entry.claim.asset_id).or_default();
+                totals.claims += 1;
+                totals.amount += entry.claim.amount.parse::<u128>().unwrap_or(0);
+                false
+            }
+            _ => true,
+        });
+        before - claims.len()
+    }
+
+    pub fn archived_totals(&self) -> BTreeMap<u32, ArchivedTotals> {
+        self.archived.read().unwrap().clone()
+    }
+}
+
+// Applies the ACTIVITY_RETENTION_DAYS policy once an hour
+pub async fn run_pruner(activity: Arc<ActivityLog>, retention_days: u64) {
+    let retention_ms = retention_days * 24 * 60 * 60 * 1_000;
+    let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let pruned = activity.prune_older_than(now_ms.saturating_sub(retention_ms));
+        if pruned > 0 {
+            info!(
+                "Pruned {} claims older than {} days; archived totals: {:?}",
+                pruned,
+                retention_days,
+                activity.archived_totals()
+            );
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    limit: Option<usize>,
+    cursor: Option<u64>,
+}
+
+// Public feed of recent cross-chain claims across all accounts
+pub async fn get_activity(
+    State(state): State<AppState>,
+    Query(query): Query<ActivityQuery>,
+) -> ResponseJson<ActivityPage> {
+    let limit = query.limit.unwrap_or(DEFAULT_FEED_LIMIT).clamp(1, MAX_FEED_LIMIT);
+    ResponseJson(state.activity.page(query.cursor, limit))
+}
+
+// ink! 5 uses BLAKE2b-256 of the event signature as the first topic
+fn signature_topic(signature: &str) -> [u8; 32] {
+    Blake2b::<U32>::digest(signature.as_bytes()).into()
+}
+
+fn decode_claim(event: &ContractEvent) -> Option<ClaimRecord> {
+    if event.topics.first() != Some(&signature_topic(CLAIM_INITIATED_SIGNATURE)) {
+        return None;
+    }
+    let claim = ClaimInitiated::decode(&mut &event.data[..]).ok()?;
+    Some(ClaimRecord {
+        account: claim.user,
+        block_number: event.block_number,
+        timestamp: event.block_timestamp,
+        amount: claim.amount.to_string(),
+        asset_id: claim.asset_id,
+        destination_parachain: claim.destination_parachain,
+        xcm_hash: format!("0x{}", hex::encode(claim.xcm_hash)),
+        receipt_id: claim.receipt_id,
+    })
+}
+
+fn stream_event(event: &ContractEvent, claim: Option<ClaimRecord>) -> StreamEvent {
+    let name = event.topics.first().and_then(|topic| {
+        USER_EVENT_SIGNATURES
+            .iter()
+            .find(|(_, signature)| signature_topic(signature) == *topic)
+            .map(|(name, _)| *name)
+    });
+    StreamEvent {
+        event: name.unwrap_or("Other"),
+        account: name
+            .and(event.data.get(..32))
+            .map(|account| format!("0x{}", hex::encode(account))),
+        block_number: event.block_number,
+        timestamp: event.block_timestamp,
+        data: Some(format!("0x{}", hex::encode(&event.data))),
+        claim,
+    }
+}
+
+// Deposited, ClaimInitiated and the other per-user vault events all lead with the
+// user's AccountId. Events that don't just clear an unrelated key, which is harmless.
+fn invalidation_for(data: &[u8]) -> Invalidation {
+    let account = data.get(..32).map(|bytes| {
+        let mut account_id = [0u8; 32];
+        account_id.copy_from_slice(bytes);
+        account_id
+    });
+    Invalidation { account }
+}
+
+pub async fn run(
+    chain: Arc<ChainClient>,
+    tx: mpsc::UnboundedSender<Invalidation>,
+    activity: Arc<ActivityLog>,
+    xcm: Arc<XcmTracker>,
+    events: broadcast::Sender<StreamEvent>,
+) {
+    loop {
+        let result = chain
+            .watch_contract_events(|event| {
+                let _ = tx.send(invalidation_for(&event.data));
+                let claim = decode_claim(&event);
+                if let Some(claim) = claim.clone() {
+                    xcm.track(&claim);
+                    activity.record_claim(claim);
+                }
+                // Sending only fails when no client is connected
+                let _ = events.send(stream_event(&event, claim));
+            })
+            .await;
+        match result {
+            Ok(()) => info!("Block subscription ended, resubscribing"),
+            Err(err) => warn!("Event indexer failed: {}", err),
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode;
+
+    fn claim_event(user: [u8; 32], amount: u128, block_number: u64) -> ContractEvent {
+        let data = (
+            user,
+            amount,
+            2000u32,
+            [7u8; 32],
+            0u128,
+            0u128,
+            block_number,
+            1u32,
+            user,
+            2000u32,
+            user,
+        )
+            .encode();
+        ContractEvent {
+            block_number,
+            block_timestamp: Some(block_number * 6_000),
+            topics: vec![signature_topic(CLAIM_INITIATED_SIGNATURE), [0u8; 32]],
+            data,
+        }
+    }
+
+    #[test]
+    fn decodes_claim_initiated() {
+        let record = decode_claim(&claim_event([1u8; 32], 500, 12)).unwrap();
+        assert_eq!(record.account, [1u8; 32]);
+        assert_eq!(record.block_number, 12);
+        assert_eq!(record.timestamp, Some(72_000));
+        assert_eq!(record.amount, "500");
+        assert_eq!(record.asset_id, 1);
+        assert_eq!(record.destination_parachain, 2000);
+        assert_eq!(record.xcm_hash, format!("0x{}", "07".repeat(32)));
+        assert_eq!(record.receipt_id, 12);
+    }
+
+    #[test]
+    fn ignores_other_events() {
+        let mut event = claim_event([1u8; 32], 500, 12);
+        event.topics[0] = signature_topic("Deposited(AccountId,Balance,AssetId,Timestamp,Balance,Balance,Balance)");
+        assert!(decode_claim(&event).is_none());
+    }
+
+    #[test]
+    fn activity_log_is_bounded_and_newest_first() {
+        let log = ActivityLog::default();
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        for block in 0..ACTIVITY_CAPACITY as u64 + 5 {
+            let user = if block % 2 == 0 { alice } else { bob };
+            log.record_claim(decode_claim(&claim_event(user, 1, block)).unwrap());
+        }
+
+        let recent = log.recent_claims_for(&alice, 3);
+        let blocks: Vec<u64> = recent.iter().map(|r| r.block_number).collect();
+        assert_eq!(blocks, vec![1_004, 1_002, 1_000]);
+        assert_eq!(log.claims.read().unwrap().len(), ACTIVITY_CAPACITY);
+    }
+
+    #[test]
+    fn activity_pages_follow_the_cursor() {
+        let log = ActivityLog::default();
+        for block in 0..5 {
+            log.record_claim(decode_claim(&claim_event([1u8; 32], 1, block)).unwrap());
+        }
+
+        let first = log.page(None, 2);
+        let ids: Vec<u64> = first.items.iter().map(|entry| entry.id).collect();
+        assert_eq!((ids, first.next_cursor), (vec![4, 3], Some(3)));
+
+        let last = log.page(Some(1), 2);
+        let ids: Vec<u64> = last.items.iter().map(|entry| entry.id).collect();
+        assert_eq!((ids, last.next_cursor), (vec![0], None));
+    }
+
+    #[test]
+    fn pruning_keeps_archived_totals() {
+        let log = ActivityLog::default();
+        for block in 0..4 {
+            log.record_claim(decode_claim(&claim_event([1u8; 32], 100, block)).unwrap());
+        }
+
+        // Blocks 0 and 1 are older than 12s
+        assert_eq!(log.prune_older_than(12_000), 2);
+        let blocks: Vec<u64> = log.page(None, 10).items.iter().map(|entry| entry.claim.block_number).collect();
+        assert_eq!(blocks, vec![3, 2]);
+        assert_eq!(log.archived_totals()[&1], ArchivedTotals { claims: 2, amount: 200 });
+    }
+}
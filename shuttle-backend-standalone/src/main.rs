@@ -1,12 +1,68 @@
+mod account;
+mod admin;
+mod cache;
+mod chain;
+mod destinations;
+mod error;
+mod indexer;
+mod dry_run;
+mod metadata;
+mod nonce;
+mod pool;
+mod reconciliation;
+mod storage_watcher;
+mod ss58;
+mod stats;
+mod ws;
+mod xcm_status;
+
 use axum::{
     routing::{get, post},
-    extract::Json,
+    extract::{Json, Query, State},
     response::Json as ResponseJson,
     Router,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tracing::info;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+use tracing::{info, warn};
+
+use cache::{cache_get, cache_put, VaultCache};
+use chain::{ChainClient, ContractClaimPreview, ContractDepositInfo};
+use destinations::DestinationAllowlist;
+use error::{ApiError, ApiResult};
+use parity_scale_codec::Encode;
+
+#[derive(Clone)]
+struct AppState {
+    // None when no chain is configured (demo mode)
+    chain: Option<Arc<ChainClient>>,
+    // Network prefix that request addresses must be encoded with
+    ss58_prefix: u16,
+    cache: Arc<VaultCache>,
+    // In-memory storage for demo purposes
+    vesting_data: Arc<RwLock<HashMap<String, VestingInfo>>>,
+    reconciliation: Arc<RwLock<reconciliation::ReconciliationReport>>,
+    // Recent vault activity seen by the indexer
+    activity: Arc<indexer::ActivityLog>,
+    // Claim destinations allowed per asset, reloaded from CLAIM_DESTINATIONS_FILE
+    claim_destinations: Arc<RwLock<DestinationAllowlist>>,
+    // Claims whose XCM delivery is not confirmed yet
+    xcm: Arc<xcm_status::XcmTracker>,
+    // Bearer token for the /admin endpoints; they are disabled without it
+    admin_api_key: Option<String>,
+    // Vault events fanned out to /ws/events connections
+    events: tokio::sync::broadcast::Sender<ws::StreamEvent>,
+    // WS_API_KEYS, each bound to the account it may follow
+    ws_api_keys: Arc<HashMap<String, ws::KeyScope>>,
+    // Loaded from CONTRACT_METADATA_FILE
+    contract_metadata: Option<Arc<metadata::ContractMetadata>>,
+    // Last good /stats numbers, served stale while the chain is unreachable
+    stats: Arc<stats::StatsCache>,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ClaimRequest {
@@ -23,26 +79,106 @@ struct ClaimResponse {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+struct BatchClaimRequest {
+    claims: Vec<ClaimRequest>,
+    #[serde(default = "default_atomic")]
+    atomic: bool,
+}
+
+fn default_atomic() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchClaimItem {
+    user_account: String,
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchClaimResponse {
+    success: bool,
+    message: String,
+    extrinsic_hash: Option<String>,
+    results: Vec<BatchClaimItem>,
+}
+
+// u128 amounts are returned as strings so JSON clients don't lose precision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AssetBreakdown {
+    asset_id: u32,
+    symbol: String,
+    locked: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeeEstimateQuery {
+    account: String,
+    destination: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FeeEstimateResponse {
+    destination_parachain: u32,
+    execution_fee: String,
+    fee_credit_used: String,
+    claim_fee: String,
+    net: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateAddressQuery {
+    address: String,
+    // SS58 prefix to check against; defaults to the configured network
+    network: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ValidateAddressResponse {
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TotalLockedResponse {
+    total_locked: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct VestingInfo {
     amount: u128,
     unlock_timestamp: u64,
     is_claimed: bool,
 }
 
-// In-memory storage for demo purposes
-static mut VESTING_DATA: Option<HashMap<String, VestingInfo>> = None;
-
 async fn hello_world() -> &'static str {
     "Cross-Chain Vesting Vault API - Ready for XCM!"
 }
 
 async fn initiate_xcm_claim(
+    State(state): State<AppState>,
     Json(request): Json<ClaimRequest>,
-) -> ResponseJson<ClaimResponse> {
+) -> ApiResult<ResponseJson<ClaimResponse>> {
     info!("Initiating XCM claim for user: {}", request.user_account);
+
+    let account_id = ss58::decode_for_network(&request.user_account, state.ss58_prefix)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid user_account: {}", err)))?;
+
+    // Refuse routes the allowlist rules out before any extrinsic is built
+    if let Some(chain) = state.chain.as_ref() {
+        if let Some(deposit) = cached_deposit(&state, chain, account_id).await? {
+            check_claim_destination(&state, deposit.asset_id, &request.destination_parachain)?;
+        }
+    }
     
     // Simulate XCM cross-chain transfer
-    let xcm_hash = format!("xcm_{}", hex::encode(&request.user_account.as_bytes()[..8]));
+    let xcm_hash = format!("xcm_{}", hex::encode(&account_id[..8]));
     
     // In a real implementation, this would:
     // 1. Verify user has unlocked tokens in the ink! contract
@@ -54,34 +190,274 @@ async fn initiate_xcm_claim(
         request.amount, request.destination_parachain, xcm_hash
     );
     
-    ResponseJson(ClaimResponse {
+    Ok(ResponseJson(ClaimResponse {
         success: true,
         message: format!(
             "XCM claim initiated for {} tokens to {}", 
             request.amount, request.destination_parachain
         ),
         xcm_hash: Some(xcm_hash),
+    }))
+}
+
+async fn initiate_xcm_claim_batch(
+    State(state): State<AppState>,
+    Json(request): Json<BatchClaimRequest>,
+) -> ApiResult<ResponseJson<BatchClaimResponse>> {
+    let chain = require_chain(&state)?;
+
+    if request.claims.is_empty() {
+        return Err(ApiError::BadRequest("No claims provided".to_string()));
+    }
+
+    let owners = request
+        .claims
+        .iter()
+        .map(|claim| {
+            ss58::decode_for_network(&claim.user_account, state.ss58_prefix).map_err(|err| {
+                ApiError::BadRequest(format!("Invalid user_account {}: {}", claim.user_account, err))
+            })
+        })
+        .collect::<ApiResult<Vec<[u8; 32]>>>()?;
+
+    info!("Submitting batch of {} XCM claims", request.claims.len());
+
+    let calls = batch_claim_calls(&owners);
+    let outcome = chain.submit_batch(calls, request.atomic).await?;
+    let results: Vec<BatchClaimItem> = request
+        .claims
+        .iter()
+        .enumerate()
+        .map(|(index, claim)| {
+            let result = outcome.results.get(index);
+            BatchClaimItem {
+                user_account: claim.user_account.clone(),
+                success: result.map(|r| r.success).unwrap_or(false),
+                error: match result {
+                    Some(r) => r.error.clone(),
+                    None => Some("Call was not executed".to_string()),
+                },
+            }
+        })
+        .collect();
+    let succeeded = results.iter().filter(|r| r.success).count();
+
+    Ok(ResponseJson(BatchClaimResponse {
+        success: succeeded == results.len(),
+        message: format!("{} of {} claims succeeded", succeeded, results.len()),
+        extrinsic_hash: Some(outcome.extrinsic_hash),
+        results,
+    }))
+}
+
+// One `claim_for(owner)` per account. The calls run as the backend signer, so each
+// owner must have approved it with `approve_claimer`; funds go to the stored destination.
+fn batch_claim_calls(owners: &[[u8; 32]]) -> Vec<Vec<u8>> {
+    owners
+        .iter()
+        .map(|owner| ChainClient::message_data("claim_for", &owner.encode()))
+        .collect()
+}
+
+fn check_claim_destination(state: &AppState, asset_id: u32, destination: &str) -> ApiResult<()> {
+    let allowlist = state.claim_destinations.read().unwrap();
+    if !allowlist.restricts(asset_id) {
+        return Ok(());
+    }
+    let allowed = destination
+        .parse()
+        .map(|parachain| allowlist.allows(asset_id, parachain))
+        .unwrap_or(false);
+    if !allowed {
+        return Err(ApiError::Unprocessable(format!(
+            "{} cannot be claimed to destination {}",
+            asset_symbol(asset_id),
+            destination
+        )));
+    }
+    Ok(())
+}
+
+async fn cached_deposit(
+    state: &AppState,
+    chain: &ChainClient,
+    account_id: [u8; 32],
+) -> ApiResult<Option<ContractDepositInfo>> {
+    if let Some(deposit) = state.cache.get_deposit(&account_id) {
+        return Ok(deposit);
+    }
+    let deposit: Option<ContractDepositInfo> = chain.query("get_deposit_info", &account_id.encode()).await?;
+    state.cache.put_deposit(account_id, deposit.clone());
+    Ok(deposit)
+}
+
+fn asset_symbol(asset_id: u32) -> &'static str {
+    match asset_id {
+        1 => "DOT",
+        2 => "USDT",
+        _ => "UNKNOWN",
+    }
+}
+
+fn require_chain(state: &AppState) -> ApiResult<&Arc<ChainClient>> {
+    state
+        .chain
+        .as_ref()
+        .ok_or_else(|| ApiError::Unavailable("Chain client not configured".to_string()))
+}
+
+async fn get_total_locked(
+    State(state): State<AppState>,
+) -> ApiResult<ResponseJson<TotalLockedResponse>> {
+    let total_locked = match cache_get(&state.cache.total_locked) {
+        Some(total_locked) => total_locked,
+        None => {
+            let total_locked: u128 = require_chain(&state)?
+                .query("get_total_locked", &[])
+                .await?;
+            cache_put(&state.cache.total_locked, total_locked);
+            total_locked
+        }
+    };
+
+    Ok(ResponseJson(TotalLockedResponse {
+        total_locked: total_locked.to_string(),
+    }))
+}
+
+async fn get_vault_breakdown(
+    State(state): State<AppState>,
+) -> ApiResult<ResponseJson<Vec<AssetBreakdown>>> {
+    if let Some(breakdown) = cache_get(&state.cache.breakdown) {
+        return Ok(ResponseJson(breakdown));
+    }
+
+    let chain = require_chain(&state)?;
+    // AssetId(u32) encodes exactly like a bare u32
+    let asset_ids: Vec<u32> = chain
+        .query("get_supported_assets", &[])
+        .await?;
+
+    let mut breakdown = Vec::with_capacity(asset_ids.len());
+    for asset_id in asset_ids {
+        let locked: u128 = chain
+            .query("get_locked_by_asset", &asset_id.encode())
+            .await?;
+        breakdown.push(AssetBreakdown {
+            asset_id,
+            symbol: asset_symbol(asset_id).to_string(),
+            locked: locked.to_string(),
+        });
+    }
+
+    cache_put(&state.cache.breakdown, breakdown.clone());
+    Ok(ResponseJson(breakdown))
+}
+
+// Fee quote for claiming the account's deposit, taken from the contract's `preview_claim`
+async fn estimate_xcm_fee(
+    State(state): State<AppState>,
+    Query(query): Query<FeeEstimateQuery>,
+) -> ApiResult<ResponseJson<FeeEstimateResponse>> {
+    let account_id = ss58::decode_for_network(&query.account, state.ss58_prefix)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid account: {}", err)))?;
+
+    let preview = match state.cache.get_claim_preview(&account_id) {
+        Some(preview) => preview,
+        None => {
+            let result: Result<ContractClaimPreview, u8> = require_chain(&state)?
+                .query("preview_claim", &account_id.encode())
+                .await?;
+            let preview = match result {
+                Ok(preview) => Some(preview),
+                Err(chain::ERR_NO_DEPOSIT_FOUND) => None,
+                Err(code) => {
+                    return Err(ApiError::Upstream(format!(
+                        "preview_claim failed with contract error {}",
+                        code
+                    )))
+                }
+            };
+            state.cache.put_claim_preview(account_id, preview.clone());
+            preview
+        }
+    };
+    let preview = preview.ok_or_else(|| ApiError::NotFound("Account has no deposit".to_string()))?;
+
+    // Claims always go to the destination stored with the deposit
+    if let Some(destination) = query.destination {
+        if destination != preview.destination_parachain {
+            return Err(ApiError::BadRequest(format!(
+                "Deposit is routed to parachain {}, not {}",
+                preview.destination_parachain, destination
+            )));
+        }
+    }
+
+    Ok(ResponseJson(FeeEstimateResponse {
+        destination_parachain: preview.destination_parachain,
+        execution_fee: preview.execution_fee.to_string(),
+        fee_credit_used: preview.fee_credit_used.to_string(),
+        claim_fee: preview.fee.to_string(),
+        net: preview.net.to_string(),
+    }))
+}
+
+// Invalid input is a normal answer here, so it is reported in the body with a 200
+async fn validate_address(
+    State(state): State<AppState>,
+    Query(query): Query<ValidateAddressQuery>,
+) -> ResponseJson<ValidateAddressResponse> {
+    let invalid = |reason: String| ValidateAddressResponse {
+        valid: false,
+        account_id_hex: None,
+        network: None,
+        reason: Some(reason),
+    };
+
+    let expected = match query.network.as_deref().map(str::parse::<u16>) {
+        None => state.ss58_prefix,
+        Some(Ok(prefix)) => prefix,
+        Some(Err(_)) => return ResponseJson(invalid("network must be an SS58 prefix number".to_string())),
+    };
+
+    ResponseJson(match ss58::decode_for_network(&query.address, expected) {
+        Ok(account_id) => ValidateAddressResponse {
+            valid: true,
+            account_id_hex: Some(format!("0x{}", hex::encode(account_id))),
+            network: Some(expected),
+            reason: None,
+        },
+        Err(err) => invalid(err.to_string()),
     })
 }
 
 async fn get_vesting_info(
+    State(state): State<AppState>,
     Json(account): Json<String>,
 ) -> ResponseJson<Option<VestingInfo>> {
-    unsafe {
-        if let Some(ref data) = VESTING_DATA {
-            ResponseJson(data.get(&account).cloned())
-        } else {
-            ResponseJson(None)
-        }
-    }
+    ResponseJson(state.vesting_data.read().unwrap().get(&account).cloned())
 }
 
 async fn simulate_deposit(
+    State(state): State<AppState>,
     Json(request): Json<HashMap<String, serde_json::Value>>,
-) -> ResponseJson<ClaimResponse> {
-    let account = request.get("account").unwrap().as_str().unwrap().to_string();
-    let amount = request.get("amount").unwrap().as_u64().unwrap() as u128;
-    let lock_seconds = request.get("lock_seconds").unwrap().as_u64().unwrap();
+) -> ApiResult<ResponseJson<ClaimResponse>> {
+    let field = |name: &str| {
+        request
+            .get(name)
+            .ok_or_else(|| ApiError::BadRequest(format!("Missing field: {}", name)))
+    };
+    let account = field("account")?
+        .as_str()
+        .ok_or_else(|| ApiError::BadRequest("account must be a string".to_string()))?
+        .to_string();
+    let amount = field("amount")?
+        .as_u64()
+        .ok_or_else(|| ApiError::BadRequest("amount must be an integer".to_string()))? as u128;
+    let lock_seconds = field("lock_seconds")?
+        .as_u64()
+        .ok_or_else(|| ApiError::BadRequest("lock_seconds must be an integer".to_string()))?;
     
     let unlock_timestamp = chrono::Utc::now().timestamp() as u64 + lock_seconds;
     
@@ -91,30 +467,143 @@ async fn simulate_deposit(
         is_claimed: false,
     };
     
-    unsafe {
-        if VESTING_DATA.is_none() {
-            VESTING_DATA = Some(HashMap::new());
-        }
-        VESTING_DATA.as_mut().unwrap().insert(account.clone(), vesting_info);
-    }
+    state
+        .vesting_data
+        .write()
+        .unwrap()
+        .insert(account.clone(), vesting_info);
     
-    ResponseJson(ClaimResponse {
+    Ok(ResponseJson(ClaimResponse {
         success: true,
         message: format!("Deposit simulated for account: {}", account),
         xcm_hash: None,
-    })
+    }))
+}
+
+async fn connect_chain(secrets: &shuttle_runtime::SecretStore) -> Option<Arc<ChainClient>> {
+    let rpc_url = secrets.get("CHAIN_RPC_URL")?;
+    let contract_address = secrets.get("CONTRACT_ADDRESS")?;
+    let signer_uri = secrets.get("SIGNER_URI")?;
+    let max_in_flight = match secrets.get("RPC_MAX_IN_FLIGHT") {
+        Some(max) => max.parse().expect("RPC_MAX_IN_FLIGHT must be a number"),
+        None => pool::DEFAULT_MAX_IN_FLIGHT,
+    };
+
+    match ChainClient::connect(&rpc_url, &contract_address, &signer_uri, max_in_flight).await {
+        Ok(client) => Some(Arc::new(client)),
+        Err(err) => {
+            warn!("Running without chain access: {}", err);
+            None
+        }
+    }
 }
 
 #[shuttle_runtime::main]
-async fn main() -> shuttle_axum::ShuttleAxum {
+async fn main(
+    #[shuttle_runtime::Secrets] secrets: shuttle_runtime::SecretStore,
+) -> shuttle_axum::ShuttleAxum {
     // Initialize tracing
     tracing_subscriber::fmt::init();
+
+    let ss58_prefix = match secrets.get("SS58_PREFIX") {
+        Some(prefix) => prefix.parse().expect("SS58_PREFIX must be a number"),
+        None => ss58::DEFAULT_PREFIX,
+    };
+
+    let state = AppState {
+        chain: connect_chain(&secrets).await,
+        ss58_prefix,
+        cache: Arc::new(VaultCache::default()),
+        vesting_data: Arc::new(RwLock::new(HashMap::new())),
+        reconciliation: Arc::new(RwLock::new(Default::default())),
+        activity: Arc::new(indexer::ActivityLog::default()),
+        claim_destinations: Arc::new(RwLock::new(DestinationAllowlist::default())),
+        xcm: Arc::new(xcm_status::XcmTracker::load(
+            secrets.get("XCM_PENDING_FILE").map(PathBuf::from),
+        )),
+        admin_api_key: secrets.get("ADMIN_API_KEY"),
+        events: ws::channel(),
+        ws_api_keys: Arc::new(match secrets.get("WS_API_KEYS") {
+            Some(spec) => ws::parse_api_keys(&spec, ss58_prefix).expect("WS_API_KEYS must be `key=address,...`"),
+            None => HashMap::new(),
+        }),
+        contract_metadata: secrets.get("CONTRACT_METADATA_FILE").map(|path| {
+            let loaded = metadata::ContractMetadata::load(path.as_ref(), secrets.get("CONTRACT_ADDRESS"))
+                .expect("CONTRACT_METADATA_FILE must be a .contract metadata file");
+            Arc::new(loaded)
+        }),
+        stats: Arc::new(stats::StatsCache::default()),
+    };
+
+    if let Some(path) = secrets.get("CLAIM_DESTINATIONS_FILE") {
+        tokio::spawn(destinations::run_reloader(
+            PathBuf::from(path),
+            state.claim_destinations.clone(),
+        ));
+    }
+
+    if let Some(days) = secrets.get("ACTIVITY_RETENTION_DAYS") {
+        let days = days.parse().expect("ACTIVITY_RETENTION_DAYS must be a number");
+        tokio::spawn(indexer::run_pruner(state.activity.clone(), days));
+    }
+
+    if let Some(chain) = state.chain.clone() {
+        let (invalidations, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(cache::run_invalidator(state.cache.clone(), rx));
+        tokio::spawn(indexer::run(
+            chain.clone(),
+            invalidations,
+            state.activity.clone(),
+            state.xcm.clone(),
+            state.events.clone(),
+        ));
+        tokio::spawn(xcm_status::run(state.xcm.clone(), chain.clone()));
+        tokio::spawn(storage_watcher::run(state.clone(), chain));
+        tokio::spawn(reconciliation::run(state.clone()));
+    }
     
     let router = Router::new()
         .route("/", get(hello_world))
+        .route("/metrics", get(pool::get_metrics))
         .route("/xcm/claim", post(initiate_xcm_claim))
+        .route("/xcm/claim/batch", post(initiate_xcm_claim_batch))
+        .route("/xcm/estimate", get(estimate_xcm_fee))
+        .route("/vault/total-locked", get(get_total_locked))
+        .route("/vault/breakdown", get(get_vault_breakdown))
+        .route("/account/{address}", get(account::get_account_summary))
+        .route("/account/{address}/pending", get(xcm_status::get_pending_for_account))
+        .route("/activity", get(indexer::get_activity))
+        .route("/stats", get(stats::get_stats))
+        .route("/ws/events", get(ws::events_socket))
+        .route("/contract/metadata", get(metadata::get_metadata))
+        .route("/contract/dry-run", post(dry_run::dry_run))
+        .route("/util/validate-address", get(validate_address))
         .route("/vesting/info", post(get_vesting_info))
-        .route("/simulate/deposit", post(simulate_deposit));
+        .route("/simulate/deposit", post(simulate_deposit))
+        .route("/admin/reconciliation", get(reconciliation::get_report))
+        .route("/admin/xcm-status", get(xcm_status::get_report))
+        .route("/admin/pause", post(admin::pause))
+        .route("/admin/unpause", post(admin::unpause))
+        .route("/admin/emergency/approve", post(admin::approve_emergency))
+        .with_state(state);
 
     Ok(router.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_claims_each_owner() {
+        let calls = batch_claim_calls(&[[1u8; 32], [2u8; 32]]);
+        let selector = &ChainClient::message_data("claim_for", &[])[..];
+
+        assert_eq!(calls.len(), 2);
+        for (call, owner) in calls.iter().zip([[1u8; 32], [2u8; 32]]) {
+            assert_eq!(&call[..4], selector);
+            assert_eq!(&call[4..], &owner[..]);
+        }
+        assert_ne!(calls[0], calls[1]);
+    }
+}
@@ -0,0 +1,107 @@
+// Serves the vault's `.contract` metadata so integrators can build their own calls.
+// Loaded once from CONTRACT_METADATA_FILE; it only changes with a new deployment.
+use std::path::Path;
+
+use axum::{
+    extract::{Query, State},
+    http::header::{CACHE_CONTROL, ETAG},
+    response::{IntoResponse, Json as ResponseJson, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    AppState,
+};
+
+const CACHE_CONTROL_VALUE: &str = "public, max-age=3600";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractMetadata {
+    contract_address: Option<String>,
+    // Wasm code hash recorded by cargo-contract in `source.hash`
+    code_hash: Option<String>,
+    metadata: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MetadataQuery {
+    // Only return the `spec` section (constructors, messages, events)
+    #[serde(default)]
+    abi_only: bool,
+}
+
+impl ContractMetadata {
+    pub fn load(path: &Path, contract_address: Option<String>) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        let metadata: Value = serde_json::from_str(&json).map_err(|err| format!("{}: {}", path.display(), err))?;
+        if metadata.get("spec").is_none() {
+            return Err(format!("{}: no `spec` section, not a contract metadata file", path.display()));
+        }
+        let code_hash = metadata
+            .pointer("/source/hash")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        Ok(Self {
+            contract_address,
+            code_hash,
+            metadata,
+        })
+    }
+
+    // None when the contract has no message by that label
+    pub fn message_mutates(&self, label: &str) -> Option<bool> {
+        self.metadata
+            .pointer("/spec/messages")?
+            .as_array()?
+            .iter()
+            .find(|message| message["label"].as_str() == Some(label))
+            .map(|message| message["mutates"].as_bool().unwrap_or(true))
+    }
+
+    fn abi_only(&self) -> Self {
+        Self {
+            metadata: self.metadata["spec"].clone(),
+            ..self.clone()
+        }
+    }
+}
+
+pub async fn get_metadata(
+    State(state): State<AppState>,
+    Query(query): Query<MetadataQuery>,
+) -> ApiResult<Response> {
+    let loaded = state
+        .contract_metadata
+        .as_deref()
+        .ok_or_else(|| ApiError::NotFound("Contract metadata not configured".to_string()))?;
+    let body = if query.abi_only { loaded.abi_only() } else { loaded.clone() };
+
+    let mut response = ResponseJson(&body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(CACHE_CONTROL, CACHE_CONTROL_VALUE.parse().expect("valid header"));
+    if let Some(etag) = body.code_hash.as_ref().and_then(|hash| format!("\"{}\"", hash).parse().ok()) {
+        headers.insert(ETAG, etag);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_code_hash_and_abi() {
+        let path = std::env::temp_dir().join(format!("vault_metadata_{}.contract", std::process::id()));
+        std::fs::write(&path, r#"{"source":{"hash":"0xabc"},"spec":{"messages":[]},"version":5}"#).unwrap();
+
+        let metadata = ContractMetadata::load(&path, Some("5Vault".to_string())).unwrap();
+        assert_eq!(metadata.code_hash.as_deref(), Some("0xabc"));
+        assert_eq!(metadata.abi_only().metadata, serde_json::json!({"messages": []}));
+
+        std::fs::write(&path, r#"{"version":5}"#).unwrap();
+        assert!(ContractMetadata::load(&path, None).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+// Local nonce tracking for the backend signer. Letting subxt look the nonce up
+// per extrinsic races under concurrency: two submissions read the same value and
+// one of them is rejected as stale.
+use std::future::Future;
+
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+pub struct NonceTracker {
+    next: Mutex<Option<u64>>,
+}
+
+impl NonceTracker {
+    // Returns the nonce for the next extrinsic, calling `fetch` for the on-chain
+    // value the first time and after every resync
+    pub async fn reserve<F, Fut, E>(&self, fetch: F) -> Result<u64, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u64, E>>,
+    {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => fetch().await?,
+        };
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    // Forgets the local value so the next reservation reads the chain again
+    pub async fn resync(&self) {
+        *self.next.lock().await = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    async fn reserve_counting(tracker: &NonceTracker, fetches: &AtomicUsize, on_chain: u64) -> u64 {
+        tracker
+            .reserve(|| async {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, ()>(on_chain)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn concurrent_submissions_get_unique_nonces() {
+        let tracker = Arc::new(NonceTracker::default());
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..20)
+            .map(|_| {
+                let tracker = tracker.clone();
+                let fetches = fetches.clone();
+                tokio::spawn(async move { reserve_counting(&tracker, &fetches, 7).await })
+            })
+            .collect();
+
+        let mut nonces = Vec::new();
+        for handle in handles {
+            nonces.push(handle.await.unwrap());
+        }
+        nonces.sort_unstable();
+
+        assert_eq!(nonces, (7..27).collect::<Vec<_>>());
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn resync_refetches_from_chain() {
+        let tracker = NonceTracker::default();
+        let fetches = AtomicUsize::new(0);
+
+        assert_eq!(reserve_counting(&tracker, &fetches, 3).await, 3);
+        assert_eq!(reserve_counting(&tracker, &fetches, 3).await, 4);
+
+        // e.g. a submission was rejected as stale and the chain moved on
+        tracker.resync().await;
+        assert_eq!(reserve_counting(&tracker, &fetches, 10).await, 10);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_leaves_tracker_unset() {
+        let tracker = NonceTracker::default();
+        assert_eq!(tracker.reserve(|| async { Err::<u64, _>("rpc down") }).await, Err("rpc down"));
+        assert_eq!(tracker.reserve(|| async { Ok::<_, ()>(5) }).await, Ok(5));
+    }
+}
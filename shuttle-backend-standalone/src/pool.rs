@@ -0,0 +1,116 @@
+// Caps how many RPC calls the backend has in flight against its node, so a burst
+// of requests queues here instead of piling onto the node. A call that can't get
+// a slot within the queue timeout is refused, which handlers answer with a 503.
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use axum::{
+    extract::State,
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::AppState;
+
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+
+// How long a call may wait for a free slot before it is refused
+pub const QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSaturated {
+    pub max: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    pub max: usize,
+    pub in_use: usize,
+    // Calls refused since startup because every slot stayed busy
+    pub rejected: u64,
+}
+
+pub struct RpcPool {
+    slots: Semaphore,
+    max: usize,
+    queue_timeout: Duration,
+    rejected: AtomicU64,
+}
+
+impl RpcPool {
+    pub fn new(max: usize, queue_timeout: Duration) -> Self {
+        Self {
+            slots: Semaphore::new(max),
+            max,
+            queue_timeout,
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    // The slot is released when the permit is dropped
+    pub async fn acquire(&self) -> Result<SemaphorePermit<'_>, PoolSaturated> {
+        match tokio::time::timeout(self.queue_timeout, self.slots.acquire()).await {
+            Ok(Ok(permit)) => Ok(permit),
+            // Timed out, or the semaphore was closed, which never happens here
+            _ => {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                Err(PoolSaturated { max: self.max })
+            }
+        }
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            max: self.max,
+            in_use: self.max - self.slots.available_permits(),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn render_metrics(stats: &PoolStats) -> String {
+    format!(
+        "# HELP vault_rpc_pool_max Maximum RPC calls in flight against the node\n\
+         # TYPE vault_rpc_pool_max gauge\n\
+         vault_rpc_pool_max {}\n\
+         # HELP vault_rpc_pool_in_use RPC calls currently in flight\n\
+         # TYPE vault_rpc_pool_in_use gauge\n\
+         vault_rpc_pool_in_use {}\n\
+         # HELP vault_rpc_pool_rejected_total RPC calls refused because the pool stayed full\n\
+         # TYPE vault_rpc_pool_rejected_total counter\n\
+         vault_rpc_pool_rejected_total {}\n",
+        stats.max, stats.in_use, stats.rejected
+    )
+}
+
+// Prometheus text format; empty in demo mode, where there is no node to protect
+pub async fn get_metrics(State(state): State<AppState>) -> Response {
+    let body = state
+        .chain
+        .as_ref()
+        .map(|chain| render_metrics(&chain.pool_stats()))
+        .unwrap_or_default();
+    ([(CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn refuses_calls_once_every_slot_is_held() {
+        let pool = RpcPool::new(2, Duration::from_millis(20));
+        let first = pool.acquire().await.unwrap();
+        let _second = pool.acquire().await.unwrap();
+
+        assert_eq!(pool.acquire().await.unwrap_err(), PoolSaturated { max: 2 });
+        assert_eq!(pool.stats(), PoolStats { max: 2, in_use: 2, rejected: 1 });
+
+        drop(first);
+        assert!(pool.acquire().await.is_ok());
+        assert!(render_metrics(&pool.stats()).contains("vault_rpc_pool_rejected_total 1\n"));
+    }
+}
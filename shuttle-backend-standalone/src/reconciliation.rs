@@ -0,0 +1,134 @@
+// Periodically compares simulated deposits with what the contract actually holds
+use std::time::Duration;
+
+use axum::{extract::State, http::HeaderMap, response::Json as ResponseJson};
+use parity_scale_codec::Encode;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::{admin, chain::ContractDepositInfo, error::ApiResult, ss58, AppState, VestingInfo};
+
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(60);
+
+// Local unlock times are wall-clock estimates, so small differences are expected
+const UNLOCK_TOLERANCE_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciliationMismatch {
+    pub account: String,
+    pub field: String,
+    pub local: String,
+    pub on_chain: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconciliationReport {
+    pub generated_at: Option<u64>,
+    pub accounts_checked: usize,
+    pub mismatches: Vec<ReconciliationMismatch>,
+}
+
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(RECONCILE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let report = reconcile(&state).await;
+        if !report.mismatches.is_empty() {
+            warn!(
+                "Reconciliation found {} mismatches across {} accounts",
+                report.mismatches.len(),
+                report.accounts_checked
+            );
+        } else {
+            info!("Reconciliation clean across {} accounts", report.accounts_checked);
+        }
+        *state.reconciliation.write().unwrap() = report;
+    }
+}
+
+async fn reconcile(state: &AppState) -> ReconciliationReport {
+    let mut report = ReconciliationReport {
+        generated_at: Some(chrono::Utc::now().timestamp() as u64),
+        ..Default::default()
+    };
+    let Some(chain) = state.chain.as_ref() else {
+        return report;
+    };
+
+    // Snapshot so the lock isn't held across chain calls
+    let local: Vec<(String, VestingInfo)> = state
+        .vesting_data
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(account, info)| (account.clone(), info.clone()))
+        .collect();
+    report.accounts_checked = local.len();
+
+    for (account, info) in local {
+        let mut flag = |field: &str, local: String, on_chain: String| {
+            report.mismatches.push(ReconciliationMismatch {
+                account: account.clone(),
+                field: field.to_string(),
+                local,
+                on_chain,
+            });
+        };
+
+        let account_id = match ss58::decode_for_network(&account, state.ss58_prefix) {
+            Ok(account_id) => account_id,
+            Err(err) => {
+                flag("account", account.clone(), format!("not queryable: {}", err));
+                continue;
+            }
+        };
+
+        let on_chain = match state.cache.get_deposit(&account_id) {
+            Some(on_chain) => on_chain,
+            None => match chain
+                .query::<Option<ContractDepositInfo>>("get_deposit_info", &account_id.encode())
+                .await
+            {
+                Ok(on_chain) => {
+                    state.cache.put_deposit(account_id, on_chain.clone());
+                    on_chain
+                }
+                Err(err) => {
+                    flag("account", account.clone(), format!("query failed: {}", err));
+                    continue;
+                }
+            },
+        };
+
+        match (info.is_claimed, on_chain) {
+            (false, None) => flag("is_claimed", "false".into(), "no deposit".into()),
+            (true, Some(_)) => flag("is_claimed", "true".into(), "still locked".into()),
+            (true, None) => {}
+            (false, Some(deposit)) => {
+                let on_chain_amount = deposit.amount + deposit.reserved_fee;
+                if on_chain_amount != info.amount {
+                    flag("amount", info.amount.to_string(), on_chain_amount.to_string());
+                }
+                // The contract stores millisecond timestamps
+                let on_chain_unlock = deposit.unlock_timestamp / 1000;
+                if on_chain_unlock.abs_diff(info.unlock_timestamp) > UNLOCK_TOLERANCE_SECS {
+                    flag(
+                        "unlock_timestamp",
+                        info.unlock_timestamp.to_string(),
+                        on_chain_unlock.to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    report
+}
+
+pub async fn get_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<ResponseJson<ReconciliationReport>> {
+    admin::require_admin_key(&state, &headers)?;
+    Ok(ResponseJson(state.reconciliation.read().unwrap().clone()))
+}
@@ -0,0 +1,115 @@
+// SS58 address decoding (https://docs.substrate.io/reference/address-formats/)
+use std::fmt;
+
+use blake2::{Blake2b512, Digest};
+
+// Generic Substrate prefix, used when no network is configured
+pub const DEFAULT_PREFIX: u16 = 42;
+
+const CHECKSUM_LEN: usize = 2;
+const ACCOUNT_ID_LEN: usize = 32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Ss58Error {
+    InvalidBase58,
+    InvalidLength,
+    InvalidPrefix,
+    InvalidChecksum,
+    WrongNetwork { expected: u16, found: u16 },
+}
+
+impl fmt::Display for Ss58Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ss58Error::InvalidBase58 => write!(f, "address is not valid base58"),
+            Ss58Error::InvalidLength => write!(f, "address has an invalid length"),
+            Ss58Error::InvalidPrefix => write!(f, "address has an invalid network prefix"),
+            Ss58Error::InvalidChecksum => write!(f, "address checksum does not match"),
+            Ss58Error::WrongNetwork { expected, found } => write!(
+                f,
+                "address is for network prefix {} but {} was expected",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Ss58Error {}
+
+// Decodes an SS58 string into its network prefix and 32-byte account id
+pub fn decode(address: &str) -> Result<(u16, [u8; 32]), Ss58Error> {
+    let data = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| Ss58Error::InvalidBase58)?;
+    if data.len() < 2 {
+        return Err(Ss58Error::InvalidLength);
+    }
+
+    let (prefix_len, prefix) = match data[0] {
+        0..=63 => (1, data[0] as u16),
+        64..=127 => {
+            let lower = (data[0] << 2) | (data[1] >> 6);
+            let upper = data[1] & 0b0011_1111;
+            (2, (lower as u16) | ((upper as u16) << 8))
+        }
+        _ => return Err(Ss58Error::InvalidPrefix),
+    };
+
+    if data.len() != prefix_len + ACCOUNT_ID_LEN + CHECKSUM_LEN {
+        return Err(Ss58Error::InvalidLength);
+    }
+
+    let body_len = prefix_len + ACCOUNT_ID_LEN;
+    let mut hasher = Blake2b512::new();
+    hasher.update(b"SS58PRE");
+    hasher.update(&data[..body_len]);
+    let hash = hasher.finalize();
+    if hash[..CHECKSUM_LEN] != data[body_len..] {
+        return Err(Ss58Error::InvalidChecksum);
+    }
+
+    let mut account_id = [0u8; 32];
+    account_id.copy_from_slice(&data[prefix_len..body_len]);
+    Ok((prefix, account_id))
+}
+
+// Decodes an address and requires it to belong to the expected network
+pub fn decode_for_network(address: &str, expected: u16) -> Result<[u8; 32], Ss58Error> {
+    let (prefix, account_id) = decode(address)?;
+    if prefix != expected {
+        return Err(Ss58Error::WrongNetwork { expected, found: prefix });
+    }
+    Ok(account_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_HEX: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn decodes_generic_substrate_address() {
+        let (prefix, account_id) = decode(ALICE).unwrap();
+        assert_eq!(prefix, DEFAULT_PREFIX);
+        assert_eq!(hex::encode(account_id), ALICE_HEX);
+    }
+
+    #[test]
+    fn rejects_wrong_network() {
+        assert_eq!(
+            decode_for_network(ALICE, 0),
+            Err(Ss58Error::WrongNetwork { expected: 0, found: DEFAULT_PREFIX })
+        );
+    }
+
+    #[test]
+    fn rejects_bad_checksum_and_garbage() {
+        let mut tampered = ALICE.to_string();
+        tampered.replace_range(10..11, "b");
+        assert_eq!(decode(&tampered), Err(Ss58Error::InvalidChecksum));
+        assert_eq!(decode("abc"), Err(Ss58Error::InvalidLength));
+        assert_eq!(decode("0OIl"), Err(Ss58Error::InvalidBase58));
+    }
+}
@@ -0,0 +1,145 @@
+// Public vault numbers for the marketing dashboard. The chain is read at most once
+// per STATS_TTL however often the page is hit, and if a refresh fails the last good
+// numbers are served with `stale: true` instead of an error.
+use std::{
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::State,
+    http::header::CACHE_CONTROL,
+    response::{IntoResponse, Json as ResponseJson, Response},
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{
+    chain::{ChainClient, ChainError},
+    error::{ApiError, ApiResult},
+    require_chain, AppState,
+};
+
+const STATS_TTL: Duration = Duration::from_secs(30);
+const CACHE_CONTROL_VALUE: &str = "public, max-age=30";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VaultStats {
+    tvl: String,
+    // Accounts holding a deposit or a schedule
+    active_deposits: u32,
+    // Supported assets with funds locked
+    distinct_assets: u32,
+    total_claimed: String,
+    stale: bool,
+}
+
+#[derive(Default)]
+pub struct StatsCache {
+    // Last good numbers and when the chain was last tried for them
+    last_good: RwLock<Option<(Instant, VaultStats)>>,
+}
+
+impl StatsCache {
+    fn fresh(&self) -> Option<VaultStats> {
+        match self.last_good.read().unwrap().as_ref() {
+            Some((fetched_at, stats)) if fetched_at.elapsed() < STATS_TTL => Some(stats.clone()),
+            _ => None,
+        }
+    }
+
+    fn store(&self, stats: VaultStats) {
+        *self.last_good.write().unwrap() = Some((Instant::now(), stats));
+    }
+
+    fn stale(&self) -> Option<VaultStats> {
+        self.last_good.read().unwrap().as_ref().map(|(_, stats)| VaultStats {
+            stale: true,
+            ..stats.clone()
+        })
+    }
+
+    // A failed refresh re-caches the last good value as stale, so an unreachable
+    // node is retried once per TTL rather than on every hit
+    fn resolve(&self, refreshed: Result<VaultStats, ApiError>) -> ApiResult<VaultStats> {
+        let stats = match refreshed {
+            Ok(stats) => stats,
+            Err(err) => self.stale().ok_or(err)?,
+        };
+        self.store(stats.clone());
+        Ok(stats)
+    }
+}
+
+async fn read_stats(chain: &ChainClient) -> Result<VaultStats, ChainError> {
+    let tvl: u128 = chain.query("get_total_locked", &[]).await?;
+    let active_deposits: u32 = chain.query("get_depositor_count", &[]).await?;
+    // AssetId(u32) encodes exactly like a bare u32
+    let active_assets: Vec<(u32, u128)> = chain.query("active_assets", &[]).await?;
+    let total_claimed: u128 = chain.query("get_total_claimed", &[]).await?;
+
+    Ok(VaultStats {
+        tvl: tvl.to_string(),
+        active_deposits,
+        distinct_assets: active_assets.len() as u32,
+        total_claimed: total_claimed.to_string(),
+        stale: false,
+    })
+}
+
+// Never requires auth
+pub async fn get_stats(State(state): State<AppState>) -> ApiResult<Response> {
+    let stats = match state.stats.fresh() {
+        Some(stats) => stats,
+        None => {
+            let refreshed = match require_chain(&state) {
+                Ok(chain) => read_stats(chain).await.map_err(|err| {
+                    warn!("Stats refresh failed: {}", err);
+                    ApiError::from(err)
+                }),
+                Err(err) => Err(err),
+            };
+            state.stats.resolve(refreshed)?
+        }
+    };
+
+    let mut response = ResponseJson(stats).into_response();
+    response
+        .headers_mut()
+        .insert(CACHE_CONTROL, CACHE_CONTROL_VALUE.parse().expect("valid header"));
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(tvl: u128) -> VaultStats {
+        VaultStats {
+            tvl: tvl.to_string(),
+            active_deposits: 3,
+            distinct_assets: 2,
+            total_claimed: "500".to_string(),
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn failed_refresh_serves_last_good_as_stale() {
+        let cache = StatsCache::default();
+        let unreachable = || Err(ApiError::Upstream("node unreachable".to_string()));
+
+        // Nothing to fall back on yet
+        assert!(cache.resolve(unreachable()).is_err());
+
+        assert_eq!(cache.resolve(Ok(stats(1_000))).unwrap(), stats(1_000));
+        assert_eq!(cache.fresh(), Some(stats(1_000)));
+
+        let served = cache.resolve(unreachable()).unwrap();
+        assert!(served.stale);
+        assert_eq!(served.tvl, "1000");
+        assert_eq!(cache.fresh(), Some(served));
+
+        assert!(!cache.resolve(Ok(stats(2_000))).unwrap().stale);
+    }
+}
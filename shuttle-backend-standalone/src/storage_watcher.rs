@@ -0,0 +1,32 @@
+// Refreshes the cached total_locked whenever the contract's storage root changes,
+// so /vault/total-locked stays current without polling the contract
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::{cache::cache_put, chain::ChainClient, AppState};
+
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+pub async fn run(state: AppState, chain: Arc<ChainClient>) {
+    loop {
+        let (changed, mut rx) = mpsc::unbounded_channel();
+        let refresh = async {
+            while rx.recv().await.is_some() {
+                match chain.query::<u128>("get_total_locked", &[]).await {
+                    Ok(total_locked) => cache_put(&state.cache.total_locked, total_locked),
+                    Err(err) => warn!("Failed to refresh total_locked: {}", err),
+                }
+            }
+        };
+
+        // The refresh loop ends once the watcher drops its sender
+        let (result, ()) = tokio::join!(chain.watch_storage_root(changed), refresh);
+        match result {
+            Ok(()) => info!("Storage watcher subscription ended, resubscribing"),
+            Err(err) => warn!("Storage watcher failed: {}", err),
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+}
@@ -0,0 +1,274 @@
+// Live vault events over WebSocket at /ws/events. Clients authenticate with an API
+// key, either as `?api_key=` or in a first `{"api_key": ..}` message, and may filter
+// by account and event name. Each key is bound to one account (or `*` for
+// operators), so a client can't follow someone else's activity. Connections without
+// a key only receive public claim events, without account or raw data.
+//
+// WS_API_KEYS format: `key=<ss58 address>,operator-key=*`
+use std::{collections::HashMap, time::Duration};
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+
+use crate::{indexer::ClaimRecord, ss58, AppState};
+
+// Events buffered per connection before a slow client starts missing them
+pub const EVENT_BUFFER: usize = 256;
+
+// How long a client without `?api_key=` has to send its subscribe message
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// RFC 6455 close code for a policy violation
+const CLOSE_POLICY: u16 = 1008;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyScope {
+    Account([u8; 32]),
+    // Operator keys may subscribe to any account
+    Any,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamEvent {
+    pub event: &'static str,
+    // "0x.." for events that lead with the user's AccountId
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    pub block_number: u64,
+    pub timestamp: Option<u64>,
+    // SCALE-encoded event fields, hex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claim: Option<ClaimRecord>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Subscribe {
+    api_key: Option<String>,
+    // SS58 address; defaults to the key's own account
+    account: Option<String>,
+    // Comma-separated in the query string
+    events: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Public,
+    Private {
+        account: Option<[u8; 32]>,
+        events: Option<Vec<String>>,
+    },
+}
+
+pub fn parse_api_keys(spec: &str, ss58_prefix: u16) -> Result<HashMap<String, KeyScope>, String> {
+    let mut keys = HashMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (key, scope) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("expected `key=address` in `{}`", entry))?;
+        let scope = match scope.trim() {
+            "*" => KeyScope::Any,
+            address => KeyScope::Account(
+                ss58::decode_for_network(address, ss58_prefix).map_err(|err| format!("{}: {}", address, err))?,
+            ),
+        };
+        keys.insert(key.trim().to_string(), scope);
+    }
+    Ok(keys)
+}
+
+fn account_hex(account: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(account))
+}
+
+impl Filter {
+    fn resolve(subscribe: Subscribe, keys: &HashMap<String, KeyScope>, ss58_prefix: u16) -> Result<Self, String> {
+        let Some(api_key) = subscribe.api_key else {
+            return Ok(Filter::Public);
+        };
+        let scope = keys.get(&api_key).ok_or("invalid api key")?;
+        let requested = subscribe
+            .account
+            .map(|address| ss58::decode_for_network(&address, ss58_prefix).map_err(|err| err.to_string()))
+            .transpose()?;
+        let account = match (scope, requested) {
+            (KeyScope::Account(own), None) => Some(*own),
+            (KeyScope::Account(own), Some(requested)) if requested == *own => Some(*own),
+            (KeyScope::Account(_), Some(_)) => return Err("api key is not valid for that account".to_string()),
+            (KeyScope::Any, requested) => requested,
+        };
+        let events = subscribe
+            .events
+            .map(|events| events.split(',').map(|event| event.trim().to_string()).collect());
+        Ok(Filter::Private { account, events })
+    }
+
+    // The event as this connection may see it, if at all
+    fn apply(&self, event: &StreamEvent) -> Option<StreamEvent> {
+        match self {
+            Filter::Public => event.claim.as_ref().map(|claim| StreamEvent {
+                event: event.event,
+                account: None,
+                block_number: event.block_number,
+                timestamp: event.timestamp,
+                data: None,
+                claim: Some(claim.clone()),
+            }),
+            Filter::Private { account, events } => {
+                if let Some(account) = account {
+                    if event.account.as_deref() != Some(account_hex(account).as_str()) {
+                        return None;
+                    }
+                }
+                if let Some(events) = events {
+                    if !events.iter().any(|name| name == event.event) {
+                        return None;
+                    }
+                }
+                Some(event.clone())
+            }
+        }
+    }
+}
+
+pub async fn events_socket(
+    State(state): State<AppState>,
+    Query(subscribe): Query<Subscribe>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade.on_upgrade(move |socket| serve(state, subscribe, socket))
+}
+
+async fn serve(state: AppState, subscribe: Subscribe, mut socket: WebSocket) {
+    // Subscribe before the handshake so nothing emitted meanwhile is missed
+    let mut events = state.events.subscribe();
+
+    let subscribe = if subscribe.api_key.is_some() {
+        subscribe
+    } else {
+        match tokio::time::timeout(HANDSHAKE_TIMEOUT, socket.recv()).await {
+            Ok(Some(Ok(Message::Text(text)))) => match serde_json::from_str(&text) {
+                Ok(subscribe) => subscribe,
+                Err(err) => return close(socket, &format!("invalid subscribe message: {}", err)).await,
+            },
+            // No handshake: public stream
+            Err(_) => Subscribe::default(),
+            _ => return,
+        }
+    };
+
+    let filter = match Filter::resolve(subscribe, &state.ws_api_keys, state.ss58_prefix) {
+        Ok(filter) => filter,
+        Err(reason) => return close(socket, &reason).await,
+    };
+
+    loop {
+        tokio::select! {
+            received = events.recv() => match received {
+                Ok(event) => {
+                    let Some(event) = filter.apply(&event) else {
+                        continue;
+                    };
+                    let json = serde_json::to_string(&event).expect("stream event serializes");
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => warn!("WebSocket client lagged, skipped {} events", skipped),
+                Err(RecvError::Closed) => return,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn close(mut socket: WebSocket, reason: &str) {
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame {
+            code: CLOSE_POLICY,
+            reason: reason.to_string().into(),
+        })))
+        .await;
+}
+
+pub fn channel() -> broadcast::Sender<StreamEvent> {
+    broadcast::channel(EVENT_BUFFER).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &'static str, account: [u8; 32]) -> StreamEvent {
+        StreamEvent {
+            event: name,
+            account: Some(account_hex(&account)),
+            block_number: 1,
+            timestamp: None,
+            data: Some("0x00".to_string()),
+            claim: None,
+        }
+    }
+
+    #[test]
+    fn keys_are_scoped_to_their_account() {
+        let alice = [1u8; 32];
+        let keys = HashMap::from([
+            ("alice-key".to_string(), KeyScope::Account(alice)),
+            ("ops-key".to_string(), KeyScope::Any),
+        ]);
+        let with_key = |key: &str| Subscribe {
+            api_key: Some(key.to_string()),
+            ..Default::default()
+        };
+
+        let filter = Filter::resolve(with_key("alice-key"), &keys, 42).unwrap();
+        assert!(filter.apply(&event("Deposited", alice)).is_some());
+        assert!(filter.apply(&event("Deposited", [2u8; 32])).is_none());
+        assert!(Filter::resolve(with_key("wrong"), &keys, 42).is_err());
+
+        let ops = Filter::resolve(
+            Subscribe {
+                events: Some("ClaimInitiated".to_string()),
+                ..with_key("ops-key")
+            },
+            &keys,
+            42,
+        )
+        .unwrap();
+        assert!(ops.apply(&event("Deposited", [2u8; 32])).is_none());
+        assert!(ops.apply(&event("ClaimInitiated", [2u8; 32])).is_some());
+    }
+
+    #[test]
+    fn public_stream_strips_account_data() {
+        let mut claim = event("ClaimInitiated", [1u8; 32]);
+        assert!(Filter::Public.apply(&claim).is_none());
+
+        claim.claim = Some(ClaimRecord {
+            account: [1u8; 32],
+            block_number: 1,
+            timestamp: None,
+            amount: "5".to_string(),
+            asset_id: 1,
+            destination_parachain: 2000,
+            xcm_hash: "0x00".to_string(),
+            receipt_id: 0,
+        });
+        let public = Filter::Public.apply(&claim).unwrap();
+        assert_eq!((public.account, public.data), (None, None));
+        assert!(public.claim.is_some());
+    }
+}
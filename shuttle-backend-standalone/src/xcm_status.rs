@@ -0,0 +1,294 @@
+// Follows claims until the contract reports their XCM message as delivered or failed.
+// The pending set is written to disk so a restart doesn't lose track of in-flight claims.
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Json as ResponseJson,
+};
+use parity_scale_codec::Encode;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    admin,
+    chain::{ChainClient, ContractXcmStatus},
+    error::{ApiError, ApiResult},
+    indexer::ClaimRecord,
+    ss58, AppState,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// Claims pending longer than this are reported as stuck
+const STUCK_AFTER_SECS: u64 = 15 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingXcm {
+    pub account: String,
+    pub block_number: u64,
+    pub first_seen: u64,
+    // Block time of the claim in milliseconds; absent in files written before it was kept
+    #[serde(default)]
+    pub initiated_at: Option<u64>,
+}
+
+// One of an account's claims still awaiting XCM delivery
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingClaim {
+    pub xcm_hash: String,
+    pub block_number: u64,
+    pub initiated_at: Option<u64>,
+    // Seconds, when the backend first saw the claim
+    pub first_seen: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct XcmStatusReport {
+    pub checked_at: Option<u64>,
+    pub pending: usize,
+    // Pending for longer than STUCK_AFTER_SECS
+    pub stuck: usize,
+    // Outcomes seen since the backend started
+    pub delivered_total: u64,
+    pub failed_total: u64,
+}
+
+#[derive(Default)]
+pub struct XcmTracker {
+    // None keeps the pending set in memory only
+    path: Option<PathBuf>,
+    // Keyed by "0x.." XCM hash
+    pending: RwLock<HashMap<String, PendingXcm>>,
+    report: RwLock<XcmStatusReport>,
+}
+
+fn now_secs() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+fn parse_hash(hash: &str) -> Option<[u8; 32]> {
+    let bytes = hex::decode(hash.strip_prefix("0x")?).ok()?;
+    bytes.try_into().ok()
+}
+
+impl XcmTracker {
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let pending = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| match serde_json::from_str(&json) {
+                Ok(pending) => Some(pending),
+                Err(err) => {
+                    warn!("Discarding unreadable pending XCM file: {}", err);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self {
+            path,
+            pending: RwLock::new(pending),
+            report: RwLock::default(),
+        }
+    }
+
+    pub fn track(&self, claim: &ClaimRecord) {
+        self.pending.write().unwrap().entry(claim.xcm_hash.clone()).or_insert(PendingXcm {
+            account: hex::encode(claim.account),
+            block_number: claim.block_number,
+            first_seen: now_secs(),
+            initiated_at: claim.timestamp,
+        });
+        self.persist();
+    }
+
+    // Newest first
+    pub fn pending_for(&self, account_id: &[u8; 32]) -> Vec<PendingClaim> {
+        let account = hex::encode(account_id);
+        let mut claims: Vec<PendingClaim> = self
+            .pending
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, pending)| pending.account == account)
+            .map(|(hash, pending)| PendingClaim {
+                xcm_hash: hash.clone(),
+                block_number: pending.block_number,
+                initiated_at: pending.initiated_at,
+                first_seen: pending.first_seen,
+            })
+            .collect();
+        claims.sort_by(|a, b| b.block_number.cmp(&a.block_number));
+        claims
+    }
+
+    fn persist(&self) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let json = serde_json::to_string(&*self.pending.read().unwrap()).expect("pending set serializes");
+        if let Err(err) = std::fs::write(path, json) {
+            warn!("Failed to persist pending XCM claims to {}: {}", path.display(), err);
+        }
+    }
+
+    fn snapshot(&self) -> Vec<String> {
+        self.pending.read().unwrap().keys().cloned().collect()
+    }
+
+    // Drops settled claims and refreshes the report. `None` means the contract
+    // doesn't know the hash (yet), so the claim stays pending.
+    fn apply(&self, statuses: Vec<(String, Option<ContractXcmStatus>)>, now: u64) {
+        let mut report = self.report.write().unwrap();
+        {
+            let mut pending = self.pending.write().unwrap();
+            for (hash, status) in statuses {
+                match status {
+                    Some(ContractXcmStatus::Delivered) => {
+                        pending.remove(&hash);
+                        report.delivered_total += 1;
+                    }
+                    Some(ContractXcmStatus::Failed) => {
+                        if let Some(claim) = pending.remove(&hash) {
+                            warn!("XCM {} for account {} failed", hash, claim.account);
+                        }
+                        report.failed_total += 1;
+                    }
+                    Some(ContractXcmStatus::Pending) | None => {}
+                }
+            }
+
+            report.checked_at = Some(now);
+            report.pending = pending.len();
+            report.stuck = 0;
+            for (hash, claim) in pending.iter() {
+                let age = now.saturating_sub(claim.first_seen);
+                if age > STUCK_AFTER_SECS {
+                    report.stuck += 1;
+                    warn!("XCM {} for account {} pending for {}s", hash, claim.account, age);
+                }
+            }
+        }
+        self.persist();
+    }
+}
+
+pub async fn run(tracker: Arc<XcmTracker>, chain: Arc<ChainClient>) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let mut statuses = Vec::new();
+        for hash in tracker.snapshot() {
+            let Some(raw) = parse_hash(&hash) else {
+                continue;
+            };
+            match chain.query::<Option<ContractXcmStatus>>("get_xcm_status", &raw.encode()).await {
+                Ok(status) => statuses.push((hash, status)),
+                Err(err) => warn!("Failed to read XCM status {}: {}", hash, err),
+            }
+        }
+
+        tracker.apply(statuses, now_secs());
+        let report = tracker.report.read().unwrap().clone();
+        info!("XCM status: {} pending, {} stuck", report.pending, report.stuck);
+    }
+}
+
+pub async fn get_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<ResponseJson<XcmStatusReport>> {
+    admin::require_admin_key(&state, &headers)?;
+    Ok(ResponseJson(state.xcm.report.read().unwrap().clone()))
+}
+
+// Claims of one account still awaiting delivery; an empty list when there are none
+pub async fn get_pending_for_account(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<ResponseJson<Vec<PendingClaim>>> {
+    let account_id = ss58::decode_for_network(&address, state.ss58_prefix)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid address: {}", err)))?;
+    Ok(ResponseJson(state.xcm.pending_for(&account_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(hash_byte: u8) -> ClaimRecord {
+        ClaimRecord {
+            account: [1u8; 32],
+            block_number: 5,
+            timestamp: None,
+            amount: "100".to_string(),
+            asset_id: 1,
+            destination_parachain: 2000,
+            xcm_hash: format!("0x{}", hex::encode([hash_byte; 32])),
+            receipt_id: hash_byte as u64,
+        }
+    }
+
+    #[test]
+    fn settles_delivered_and_counts_stuck() {
+        let tracker = XcmTracker::default();
+        let (delivered, failed, stuck) = (claim(1), claim(2), claim(3));
+        for claim in [&delivered, &failed, &stuck] {
+            tracker.track(claim);
+        }
+
+        let later = now_secs() + STUCK_AFTER_SECS + 1;
+        tracker.apply(
+            vec![
+                (delivered.xcm_hash.clone(), Some(ContractXcmStatus::Delivered)),
+                (failed.xcm_hash.clone(), Some(ContractXcmStatus::Failed)),
+                (stuck.xcm_hash.clone(), Some(ContractXcmStatus::Pending)),
+            ],
+            later,
+        );
+
+        let report = tracker.report.read().unwrap().clone();
+        assert_eq!((report.pending, report.stuck), (1, 1));
+        assert_eq!((report.delivered_total, report.failed_total), (1, 1));
+        assert_eq!(tracker.snapshot(), vec![stuck.xcm_hash]);
+    }
+
+    #[test]
+    fn lists_pending_claims_per_account() {
+        let tracker = XcmTracker::default();
+        let mut older = claim(1);
+        older.block_number = 4;
+        older.timestamp = Some(24_000);
+        let newer = claim(2);
+        let mut other = claim(3);
+        other.account = [2u8; 32];
+        for claim in [&older, &newer, &other] {
+            tracker.track(claim);
+        }
+
+        let pending = tracker.pending_for(&[1u8; 32]);
+        let hashes: Vec<&str> = pending.iter().map(|claim| claim.xcm_hash.as_str()).collect();
+        assert_eq!(hashes, vec![newer.xcm_hash.as_str(), older.xcm_hash.as_str()]);
+        assert_eq!(pending[1].initiated_at, Some(24_000));
+        assert!(tracker.pending_for(&[9u8; 32]).is_empty());
+    }
+
+    #[test]
+    fn pending_set_survives_reload() {
+        let path = std::env::temp_dir().join(format!("xcm_pending_{}.json", std::process::id()));
+        let tracker = XcmTracker::load(Some(path.clone()));
+        tracker.track(&claim(9));
+
+        let reloaded = XcmTracker::load(Some(path.clone()));
+        assert_eq!(reloaded.snapshot(), vec![claim(9).xcm_hash]);
+        assert_eq!(parse_hash(&claim(9).xcm_hash), Some([9u8; 32]));
+        std::fs::remove_file(path).unwrap();
+    }
+}
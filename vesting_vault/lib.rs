@@ -4,6 +4,7 @@
 mod vesting_vault {
     use ink::storage::Mapping;
     use ink::prelude::*;
+    use scale::Encode;
     use ink::env::{
         call::{build_call, ExecutionInput, Selector},
         DefaultEnvironment,
@@ -16,27 +17,445 @@ mod vesting_vault {
         pub admin: AccountId,
         pub total_locked: Balance,
         pub supported_assets: Vec<AssetId>,
+        pub claim_fee_bps: u16,
+        pub burn_bps: u16,
+        pub treasury_balance: Balance,
+        pub total_burned: Balance,
+        pub allowlist_enabled: bool,
+        pub allowlist: Mapping<AccountId, ()>,
+        pub fee_buffer_bps: u16,
+        pub xcm_execution_fee: Balance,
+        pub min_lock: u64,
+        pub max_lock: u64,
+        pub deposits_paused: bool,
+        pub treasury: AccountId,
+        pub schedules: Mapping<AccountId, VestingSchedule>,
+        pub locked_by_asset: Mapping<AssetId, Balance>,
+        pub reconciliation_tolerance: Balance,
+        pub depositors: Mapping<u32, AccountId>,
+        pub depositor_positions: Mapping<AccountId, u32>,
+        pub depositor_count: u32,
+        pub claims_paused: bool,
+        pub outflow_breaker_enabled: bool,
+        pub outflow_threshold: Balance,
+        pub outflow_window: u64,
+        pub outflow_window_start: Timestamp,
+        pub outflow_in_window: Balance,
+        pub claim_approvals: Mapping<AccountId, AccountId>,
+        pub max_destinations_per_account: u32,
+        // Prefunded execution fees per (user, destination), with the asset they were paid in
+        pub fee_credits: Mapping<(AccountId, u32), (AssetId, Balance)>,
+        pub allowed_destinations: Vec<u32>,
+        pub emergency_toggle_cooldown: u64,
+        pub last_emergency_toggle: Option<Timestamp>,
+        pub large_deposit_threshold: Option<Balance>,
+        pub pending_deposits: Mapping<AccountId, PendingDeposit>,
+        pub emergency_triggered_at: Option<Timestamp>,
+        pub emergency_triggered_by: Option<AccountId>,
+        // Approved (asset, lock_secs) product terms
+        pub deposit_presets: Vec<(AssetId, u64)>,
+        // amount × lock length per deposit, fixed whenever the lock is (re)set
+        pub lock_weights: Mapping<AccountId, u128>,
+        pub total_lock_weight: u128,
+        // Assets backed by a token contract instead of the Assets precompile
+        pub asset_contracts: Mapping<AssetId, AccountId>,
+        // Ring buffer of (block, total_locked) after each change; slot = index % MAX_TVL_CHECKPOINTS
+        pub tvl_checkpoints: Mapping<u32, (BlockNumber, Balance)>,
+        pub tvl_checkpoint_count: u32,
+        pub deposit_fee_bps: u16,
+        pub verbose_events: bool,
+        // Sponsored-claim policy; a cap of 0 disables sponsorship
+        pub sponsor_cap: Balance,
+        pub sponsor_requires_allowlist: bool,
+        // What the sponsored-claims program may still spend; sponsorship stops at 0
+        pub sponsorship_budget: Balance,
+        // Accounts whose deposit was exported to a successor vault
+        pub migrated: Mapping<AccountId, ()>,
+        // Delivery state of each claim's XCM message, keyed by its hash
+        pub xcm_statuses: Mapping<[u8; 32], XcmStatus>,
+        pub asset_metadata: Mapping<AssetId, AssetMetadata>,
+        // Claims made since emergency mode was last triggered, against the number of
+        // depositors at trigger time; milestones are fractions of that base in bps
+        pub emergency_claim_count: u32,
+        pub emergency_depositor_base: u32,
+        pub emergency_milestones_bps: Vec<u16>,
+        // Limit on total_locked (raw units, summed across assets); None is unlimited
+        pub tvl_cap: Option<Balance>,
+        // Root cached by refresh_deposits_root; cleared whenever a deposit or the index changes
+        pub deposits_root: Option<[u8; 32]>,
+        // Guardian set for multisig-gated actions; 0 until a threshold is configured.
+        // Once set, emergency_unlock needs that many guardian approvals.
+        pub guardians: Vec<AccountId>,
+        pub guardian_threshold: u32,
+        // What each claim actually paid out, keyed by a sequential receipt id
+        pub claim_receipts: Mapping<u64, ClaimReceipt>,
+        pub next_receipt_id: u64,
+        // When each deposit's lock began, for pro-rating early exit penalties
+        pub lock_starts: Mapping<AccountId, Timestamp>,
+        // Penalty for exiting right after depositing; shrinks linearly to 0 at unlock
+        pub early_exit_penalty_bps: u16,
+        // Undistributed rewards across all assets; the per-asset pools are in reward_pools
+        pub reward_pool: Balance,
+        // Annual reward on locked principal
+        pub reward_rate_bps: u16,
+        // Where reward accrual restarts after a harvest; defaults to the lock start
+        pub reward_checkpoints: Mapping<AccountId, Timestamp>,
+        // When set, claims may only pay out to a destination the owner registered
+        pub claim_destinations_required: bool,
+        // When set, registrations wait for admin approval before they count
+        pub claim_destination_approval: bool,
+        pub allowed_claim_destinations: Mapping<AccountId, Vec<(u32, [u8; 32])>>,
+        pub pending_claim_destinations: Mapping<AccountId, Vec<(u32, [u8; 32])>>,
+        // During an emergency, pay claims to the owner's account on this chain instead of via XCM
+        pub emergency_local_payout: bool,
+        // BuyExecution asset per destination parachain; others pay in the claimed asset
+        pub fee_asset_by_destination: Mapping<u32, AssetId>,
+        // When set, claims to a destination without a configured fee asset are refused
+        pub strict_fee_assets: bool,
+        // Net paid out by claims (after fees and penalties), all assets together; never decreases
+        pub total_claimed: Balance,
+        pub claimed_by_asset: Mapping<AssetId, Balance>,
+        // Existential deposit (asset min_balance) the vault's own account must keep
+        pub asset_min_balances: Mapping<AssetId, Balance>,
+        // Dead-man's switch: after this long without admin activity anyone may force
+        // emergency mode; 0 disables it
+        pub deadman_secs: u64,
+        pub last_admin_activity: Timestamp,
+        pub rounding: RoundingMode,
+        // Next nonce each owner must sign for set_destination_signed
+        pub destination_nonces: Mapping<AccountId, u64>,
+        // A raised min_lock waits here for min_lock_grace_secs as (value, effective_at),
+        // so deposits already in flight aren't rejected; lowering applies at once
+        pub pending_min_lock: Option<(u64, Timestamp)>,
+        pub min_lock_grace_secs: u64,
+        // XCM hash of each account's latest cross-chain deposit claim
+        pub last_claim_hashes: Mapping<AccountId, [u8; 32]>,
+        // Split of treasury_balance and reward_pool by asset, so rewards are only
+        // ever funded and paid in the asset the fees were collected in
+        pub treasury_by_asset: Mapping<AssetId, Balance>,
+        pub reward_pools: Mapping<AssetId, Balance>,
+        // Guardians who approved the next emergency_unlock; cleared when it runs
+        pub emergency_approvals: Vec<AccountId>,
+        // Deposits whose unlock time the admin set; they can't be exited early
+        pub unlock_holds: Mapping<AccountId, ()>,
+        // Escrowed large deposits and prefunded fee credits per asset; the vault owes
+        // both back, so sweep_asset_dust must leave them in place
+        pub pending_by_asset: Mapping<AssetId, Balance>,
+        pub fee_credits_by_asset: Mapping<AssetId, Balance>,
+        // Exported deposits not yet sent to the successor vault, per asset
+        pub migrating_by_asset: Mapping<AssetId, Balance>,
+        // Guardian approvals for moving an account's unlock earlier, bound to the
+        // proposed unlock time; cleared when admin_set_unlock applies it
+        pub unlock_approvals: Mapping<AccountId, (Timestamp, Vec<AccountId>)>,
+        // Grantor each recipient agreed to take one grant from; used up by the grant
+        pub grant_approvals: Mapping<AccountId, AccountId>,
+        // Burned claim fees per asset. Nothing is transferred out: the tokens stay in the
+        // vault and are counted as owed, so no sweep can ever move them.
+        pub burned_by_asset: Mapping<AssetId, Balance>,
     }
 
-    #[derive(scale::Encode, scale::Decode, Clone, Debug)]
+    const MAX_BPS: u16 = 10_000;
+    const MAX_DEPOSIT_FEE_BPS: u16 = 1_000;
+    const DEFAULT_MIN_LOCK: u64 = 60_000;
+    // Prefix of the payload signed for set_destination_signed, so the signature
+    // can't be replayed as anything else
+    const SET_DESTINATION_DOMAIN: &[u8] = b"vesting_vault:set_destination";
+    const MAX_TRANCHES: usize = 48;
+    const MAX_PAGE_SIZE: u32 = 100;
+    const MAX_INDEX_SCAN: u32 = 1_000;
+    const DEFAULT_MAX_DESTINATIONS: u32 = 8;
+    const MAX_ALLOWED_DESTINATIONS: usize = 64;
+    const MAX_DEPOSIT_PRESETS: usize = 32;
+    const MAX_SUPPORTED_ASSETS: usize = 32;
+    const MAX_TVL_CHECKPOINTS: u32 = 256;
+    const MAX_SYMBOL_LEN: usize = 12;
+    const MAX_EMERGENCY_MILESTONES: usize = 8;
+    const MAX_GUARDIANS: usize = 16;
+    const MAX_CLAIM_DESTINATIONS: usize = 8;
+    const MAX_UNLOCK_BUCKETS: usize = 64;
+    // TvlCapNearlyReached fires when total_locked crosses this share of the cap
+    const TVL_CAP_WARNING_BPS: u16 = 9_000;
+    const MAX_DECIMALS: u8 = 36;
+    const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1_000;
+    // PSP22::transfer_from(from, to, value, data)
+    const PSP22_TRANSFER_FROM: [u8; 4] = [0x54, 0xb3, 0xc7, 0x6e];
+    // PSP22::balance_of(owner)
+    const PSP22_BALANCE_OF: [u8; 4] = [0x65, 0x68, 0x38, 0x2f];
+
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VaultConfig {
+        pub admin: AccountId,
+        pub treasury: AccountId,
+        pub min_lock: u64,
+        pub max_lock: u64,
+        pub claim_fee_bps: u16,
+        pub burn_bps: u16,
+        pub fee_buffer_bps: u16,
+        pub xcm_execution_fee: Balance,
+        pub deposits_paused: bool,
+        pub emergency_mode: bool,
+        pub allowlist_enabled: bool,
+        pub reconciliation_tolerance: Balance,
+        pub claims_paused: bool,
+        pub outflow_breaker_enabled: bool,
+        pub outflow_threshold: Balance,
+        pub outflow_window: u64,
+        pub max_destinations_per_account: u32,
+        pub emergency_toggle_cooldown: u64,
+        pub large_deposit_threshold: Option<Balance>,
+        pub deposit_fee_bps: u16,
+        pub verbose_events: bool,
+        pub sponsor_cap: Balance,
+        pub sponsor_requires_allowlist: bool,
+        pub sponsorship_budget: Balance,
+        pub tvl_cap: Option<Balance>,
+        pub early_exit_penalty_bps: u16,
+        pub reward_pool: Balance,
+        pub reward_rate_bps: u16,
+        pub claim_destinations_required: bool,
+        pub claim_destination_approval: bool,
+        pub emergency_local_payout: bool,
+        pub strict_fee_assets: bool,
+        pub rounding: RoundingMode,
+    }
+
+    // Fields left as None keep their current value. `tvl_cap: Some(None)` removes the cap.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ConfigPatch {
+        pub min_lock: Option<u64>,
+        pub max_lock: Option<u64>,
+        pub claim_fee_bps: Option<u16>,
+        pub burn_bps: Option<u16>,
+        pub deposit_fee_bps: Option<u16>,
+        pub fee_buffer_bps: Option<u16>,
+        pub xcm_execution_fee: Option<Balance>,
+        pub early_exit_penalty_bps: Option<u16>,
+        pub reward_rate_bps: Option<u16>,
+        pub tvl_cap: Option<Option<Balance>>,
+        pub treasury: Option<AccountId>,
+        pub rounding: Option<RoundingMode>,
+    }
+
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ConfigField {
+        MinLock,
+        MaxLock,
+        ClaimFeeBps,
+        BurnBps,
+        DepositFeeBps,
+        FeeBufferBps,
+        XcmExecutionFee,
+        EarlyExitPenaltyBps,
+        RewardRateBps,
+        TvlCap,
+        Treasury,
+        Rounding,
+    }
+
+    #[derive(scale::Encode, scale::Decode, Clone, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct DepositInfo {
         pub amount: Balance,
         pub unlock_timestamp: Timestamp,
         pub asset_id: AssetId,
         pub destination_parachain: u32,
+        pub reserved_fee: Balance,
+        pub destination_account: [u8; 32],
     }
 
-    #[derive(scale::Encode, scale::Decode, Clone, Debug)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct AssetId(pub u32);
 
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct AssetMetadata {
+        // UTF-8 ticker, e.g. b"DOT"
+        pub symbol: Vec<u8>,
+        pub decimals: u8,
+    }
+
+    // Inclusion proof against deposits_merkle_root. Leaves are hashed in depositor
+    // index order; an unpaired node at the end of a level moves up unchanged, so a
+    // verifier needs `leaf_count` to know which levels skip a sibling.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct MerkleProof {
+        pub leaf: [u8; 32],
+        pub leaf_index: u32,
+        pub leaf_count: u32,
+        pub siblings: Vec<[u8; 32]>,
+    }
+
+    // `fee` is the claim fee (treasury + burn); `execution_fee` is the destination
+    // BuyExecution cost, paid from the reserved buffer and prefunded credit
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ClaimReceipt {
+        pub owner: AccountId,
+        pub gross: Balance,
+        pub fee: Balance,
+        pub execution_fee: Balance,
+        pub net: Balance,
+        pub asset_id: AssetId,
+        pub destination_parachain: u32,
+        pub xcm_hash: [u8; 32],
+        pub timestamp: Timestamp,
+    }
+
+    // Everything the asset picker needs for one supported asset. Lock bounds, the
+    // TVL cap and the pause flag are vault-wide and repeated for convenience.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AssetConfig {
+        pub asset_id: AssetId,
+        pub metadata: Option<AssetMetadata>,
+        pub token_contract: Option<AccountId>,
+        pub locked: Balance,
+        pub min_lock: u64,
+        pub max_lock: u64,
+        pub tvl_cap: Option<Balance>,
+        pub deposits_paused: bool,
+    }
+
+    // Every claim starts Pending; the admin (or its relayer key) reports the outcome
+    // once the destination chain has processed the message
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum XcmStatus {
+        Pending,
+        Delivered,
+        Failed,
+    }
+
+    // How fee, penalty and reward math rounds a fractional result. Down is the default
+    // and favors the user on fees and penalties; Nearest rounds halves up.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum RoundingMode {
+        #[default]
+        Down,
+        Up,
+        Nearest,
+    }
+
+    // `execution_fee` is covered by the reserved buffer and prefunded credit, not by `gross`
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ClaimPreview {
+        pub gross: Balance,
+        pub fee: Balance,
+        pub execution_fee: Balance,
+        pub fee_credit_used: Balance,
+        pub reward: Balance,
+        pub net: Balance,
+        pub destination_parachain: u32,
+        pub destination_account: [u8; 32],
+    }
+
+    // Where an account's deposit stands. Claimable means a claim would go through now
+    // and carries the net payout.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum DepositState {
+        None,
+        AwaitingConfirmation { amount: Balance },
+        Locked { unlock: Timestamp },
+        // Claims are paused
+        Frozen,
+        Claimable { amount: Balance },
+        // Claimed; the XCM message hasn't been confirmed delivered yet
+        ClaimPending { xcm_hash: [u8; 32] },
+        // Matured, but a claim would fail this check (e.g. DestinationNotRegistered)
+        Blocked { reason: VestingError },
+    }
+
+    // Whether a deposit claim would go through right now, and if not, the first check
+    // that blocks it
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum EffectiveStatus {
+        NoDeposit,
+        // Moved out by export_and_mark during an upgrade
+        Migrated,
+        ClaimsPaused,
+        Locked { unlock: Timestamp },
+        DestinationNotRegistered,
+        // `emergency` marks a claim allowed only because emergency mode lifted the lock
+        Claimable { amount: Balance, emergency: bool },
+        // Any other failing check, e.g. FeeAssetNotConfigured for the destination
+        Blocked { reason: VestingError },
+    }
+
+    // The admin can't be handed over yet, so `pending_admin` is always None
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct GovernanceInfo {
+        pub admin: AccountId,
+        pub pending_admin: Option<AccountId>,
+        pub guardians: Vec<AccountId>,
+        pub threshold: u32,
+    }
+
+    // Emergency mode has no automatic lapse yet, so `expiry` is always None
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EmergencyInfo {
+        pub active: bool,
+        pub triggered_at: Option<Timestamp>,
+        pub triggered_by: Option<AccountId>,
+        pub expiry: Option<Timestamp>,
+    }
+
+    #[derive(scale::Encode, scale::Decode, Clone, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PendingDeposit {
+        pub asset_id: AssetId,
+        pub amount: Balance,
+        pub lock_secs: u64,
+        pub destination_parachain: u32,
+        pub created_at: Timestamp,
+    }
+
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    // Like a deposit, `amount` is the net principal and `reserved_fee` the tranche's
+    // share of the buffer held back for destination execution
+    pub struct Tranche {
+        pub amount: Balance,
+        pub unlock_timestamp: Timestamp,
+        pub reserved_fee: Balance,
+    }
+
+    #[derive(scale::Encode, scale::Decode, Clone, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct VestingSchedule {
+        pub asset_id: AssetId,
+        pub destination_parachain: u32,
+        pub tranches: Vec<Tranche>,
+    }
+
+    // `amount` is the net principal: gross minus deposit_fee minus reserved_fee
     #[ink(event)]
     pub struct Deposited {
         pub user: AccountId,
         pub amount: Balance,
         pub asset_id: AssetId,
         pub unlock_time: Timestamp,
+        pub reserved_fee: Balance,
+        pub gross: Balance,
+        pub deposit_fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ScheduleDeposited {
+        pub user: AccountId,
+        pub total_amount: Balance,
+        pub asset_id: AssetId,
+        pub tranche_count: u32,
     }
 
     #[ink(event)]
@@ -45,6 +464,14 @@ mod vesting_vault {
         pub amount: Balance,
         pub destination_parachain: u32,
         pub xcm_hash: [u8; 32],
+        pub fee_to_treasury: Balance,
+        pub fee_burned: Balance,
+        pub receipt_id: u64,
+        pub asset_id: AssetId,
+        // Where the claim was sent; differs from the stored destination when overridden
+        pub destination_account: [u8; 32],
+        pub stored_destination_parachain: u32,
+        pub stored_destination_account: [u8; 32],
     }
 
     #[ink(event)]
@@ -54,165 +481,3282 @@ mod vesting_vault {
     }
 
     #[ink(event)]
-    pub struct XCMExecuted {
+    pub struct DepositsPaused {
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct DepositsUnpaused {
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ClaimsPaused {
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ClaimsUnpaused {
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AssetDustSwept {
+        pub asset_id: AssetId,
+        pub to: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ReconciliationMismatch {
+        pub asset_id: AssetId,
+        pub held: Balance,
+        pub tracked: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AutoEmergencyTriggered {
+        pub outflow: Balance,
+        pub threshold: Balance,
+        pub window_start: Timestamp,
+        pub timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct ClaimerApproved {
+        pub owner: AccountId,
+        pub operator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ClaimerRevoked {
+        pub owner: AccountId,
+        pub operator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FeePrefunded {
         pub user: AccountId,
+        pub asset_id: AssetId,
         pub amount: Balance,
         pub destination: u32,
-        pub success: bool,
     }
 
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum VestingError {
-        InsufficientBalance,
-        TokensStillLocked,
-        NoDepositFound,
-        UnauthorizedAccess,
-        AssetNotSupported,
-        XCMExecutionFailed,
+    #[ink(event)]
+    pub struct DestinationChangedSigned {
+        pub account: AccountId,
+        pub old_parachain: u32,
+        pub old_account: [u8; 32],
+        pub new_parachain: u32,
+        pub new_account: [u8; 32],
+        pub nonce: u64,
+        pub relayer: AccountId,
     }
 
-    impl VestingVault {
-        #[ink(constructor)]
-        pub fn new(admin: AccountId) -> Self {
-            let mut supported_assets = Vec::new();
-            supported_assets.push(AssetId(1)); // DOT
-            supported_assets.push(AssetId(2)); // USDT
-            
-            Self {
-                deposits: Default::default(),
-                emergency_mode: false,
-                admin,
-                total_locked: 0,
-                supported_assets,
-            }
-        }
+    #[ink(event)]
+    pub struct DestinationOverridden {
+        pub account: AccountId,
+        pub old_parachain: u32,
+        pub old_account: [u8; 32],
+        pub new_parachain: u32,
+        pub new_account: [u8; 32],
+        pub admin: AccountId,
+    }
 
-        // Asset Precompile Integration
-        #[ink(message, payable)]
-        pub fn deposit_with_asset(
-            &mut self, 
-            asset_id: AssetId, 
-            amount: Balance, 
-            lock_secs: u64,
-            destination_parachain: u32,
-        ) -> Result<(), VestingError> {
-            let caller = self.env().caller();
-            let current_time = self.env().block_timestamp();
-            let unlock_time = current_time + lock_secs;
+    #[ink(event)]
+    pub struct DestinationMigrated {
+        pub old_parachain: u32,
+        pub new_parachain: u32,
+        // Accounts rewritten by this call; a large migration takes several pages
+        pub accounts: u32,
+        pub admin: AccountId,
+    }
 
-            // Validate asset support
-            if !self.supported_assets.contains(&asset_id) {
-                return Err(VestingError::AssetNotSupported);
-            }
+    #[ink(event)]
+    pub struct Heartbeat {
+        pub timestamp: Timestamp,
+        pub total_locked: Balance,
+    }
 
-            assert!(lock_secs >= 60000, "Minimum lock time is 60 seconds");
-            assert!(amount > 0, "Amount must be greater than zero");
+    // Every supported asset, including those with nothing locked
+    #[ink(event)]
+    pub struct AssetSnapshot {
+        pub entries: Vec<(AssetId, Balance)>,
+        pub timestamp: Timestamp,
+    }
 
-            // In a real implementation, this would call the Assets precompile
-            // to transfer tokens from the user to the contract
-            self.call_assets_precompile_transfer(caller, amount, asset_id.clone())?;
+    #[ink(event)]
+    pub struct DeadmanTriggered {
+        pub timestamp: Timestamp,
+        pub caller: AccountId,
+        pub last_admin_activity: Timestamp,
+    }
 
-            let info = DepositInfo {
-                amount,
-                unlock_timestamp: unlock_time,
-                asset_id: asset_id.clone(),
-                destination_parachain,
-            };
+    #[ink(event)]
+    pub struct OperationsResumed {
+        pub timestamp: Timestamp,
+        pub admin: AccountId,
+    }
 
-            self.deposits.insert(caller, &info);
-            self.total_locked += amount;
-            
-            self.env().emit_event(Deposited {
-                user: caller,
-                amount,
-                asset_id,
-                unlock_time,
-            });
+    #[ink(event)]
+    pub struct DepositPending {
+        pub user: AccountId,
+        pub amount: Balance,
+        pub asset_id: AssetId,
+        pub lock_secs: u64,
+    }
 
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct DepositConfirmed {
+        pub user: AccountId,
+        pub amount: Balance,
+        pub asset_id: AssetId,
+    }
 
-        // XCM Cross-Chain Claim
-        #[ink(message)]
-        pub fn claim_cross_chain(&mut self) -> Result<(), VestingError> {
-            let caller = self.env().caller();
-            let current_time = self.env().block_timestamp();
+    #[ink(event)]
+    pub struct DepositCancelled {
+        pub user: AccountId,
+        pub amount: Balance,
+        pub asset_id: AssetId,
+    }
 
-            let info = self.deposits.get(caller)
-                .ok_or(VestingError::NoDepositFound)?;
+    #[ink(event)]
+    pub struct UnlockAdjusted {
+        pub account: AccountId,
+        pub old: Timestamp,
+        pub new: Timestamp,
+        pub admin: AccountId,
+    }
 
-            if current_time < info.unlock_timestamp && !self.emergency_mode {
-                return Err(VestingError::TokensStillLocked);
-            }
+    #[ink(event)]
+    pub struct TvlCheckpoint {
+        pub block_number: BlockNumber,
+        pub total_locked: Balance,
+    }
 
-            // Execute XCM cross-chain transfer
-            let xcm_hash = self.execute_xcm_transfer(
-                caller,
-                info.amount,
-                info.destination_parachain,
-                info.asset_id.clone(),
-            )?;
+    #[ink(event)]
+    pub struct MigratingFundsReleased {
+        pub asset_id: AssetId,
+        pub to: AccountId,
+        pub amount: Balance,
+    }
 
-            self.total_locked -= info.amount;
-            self.deposits.remove(caller);
+    #[ink(event)]
+    pub struct DepositMigrated {
+        pub account: AccountId,
+        pub asset_id: AssetId,
+        pub amount: Balance,
+        pub reserved_fee: Balance,
+        pub unlock_timestamp: Timestamp,
+        pub admin: AccountId,
+    }
 
-            self.env().emit_event(ClaimInitiated {
-                user: caller,
-                amount: info.amount,
-                destination_parachain: info.destination_parachain,
-                xcm_hash,
-            });
+    #[ink(event)]
+    pub struct DepositImported {
+        pub account: AccountId,
+        pub asset_id: AssetId,
+        pub amount: Balance,
+        pub reserved_fee: Balance,
+        pub unlock_timestamp: Timestamp,
+        pub admin: AccountId,
+    }
 
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct XcmStatusUpdated {
+        pub xcm_hash: [u8; 32],
+        pub status: XcmStatus,
+    }
 
-        // Circuit Breaker - Emergency Withdraw
-        #[ink(message)]
-        pub fn emergency_unlock(&mut self) -> Result<(), VestingError> {
-            if self.env().caller() != self.admin {
-                return Err(VestingError::UnauthorizedAccess);
-            }
+    #[ink(event)]
+    pub struct AssetMetadataRegistered {
+        pub asset_id: AssetId,
+        pub symbol: Vec<u8>,
+        pub decimals: u8,
+    }
 
-            self.emergency_mode = true;
+    #[ink(event)]
+    pub struct ConfigUpdated {
+        // Only fields whose value actually changed
+        pub changed: Vec<ConfigField>,
+        pub admin: AccountId,
+    }
 
-            self.env().emit_event(EmergencyTriggered {
-                timestamp: self.env().block_timestamp(),
+    #[ink(event)]
+    pub struct EmergencyClaimMilestone {
+        pub milestone_bps: u16,
+        pub claims: u32,
+        pub depositors: u32,
+    }
+
+    #[ink(event)]
+    pub struct MinLockScheduled {
+        pub current: u64,
+        pub pending: u64,
+        pub effective_at: Timestamp,
+    }
+
+    // Emitted when the pending value is stored, which may be some time after
+    // effective_at; deposits enforce it from effective_at either way
+    #[ink(event)]
+    pub struct MinLockActivated {
+        pub min_lock: u64,
+        pub effective_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct TvlCapNearlyReached {
+        pub total_locked: Balance,
+        pub cap: Balance,
+    }
+
+    #[ink(event)]
+    pub struct GuardianAdded {
+        pub guardian: AccountId,
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct GuardianRemoved {
+        pub guardian: AccountId,
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ThresholdChanged {
+        pub old: u32,
+        pub new: u32,
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyApproved {
+        pub guardian: AccountId,
+        pub approvals: u32,
+        pub threshold: u32,
+    }
+
+    #[ink(event)]
+    pub struct EarlyUnlockApproved {
+        pub account: AccountId,
+        pub new_unlock: Timestamp,
+        pub guardian: AccountId,
+        pub approvals: u32,
+        pub threshold: u32,
+    }
+
+    #[ink(event)]
+    pub struct EarlyWithdrawn {
+        pub user: AccountId,
+        pub penalty_bps: u16,
+        pub penalty: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Granted {
+        pub user: AccountId,
+        pub granted_by: AccountId,
+        pub amount: Balance,
+        pub asset_id: AssetId,
+        pub unlock_time: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct RewardsClaimed {
+        pub user: AccountId,
+        pub amount: Balance,
+        pub asset_id: AssetId,
+    }
+
+    #[ink(event)]
+    pub struct ClaimDestinationRequested {
+        pub user: AccountId,
+        pub parachain: u32,
+        pub account: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct ClaimDestinationRegistered {
+        pub user: AccountId,
+        pub parachain: u32,
+        pub account: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct ClaimDestinationDeregistered {
+        pub user: AccountId,
+        pub parachain: u32,
+        pub account: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct RewardPoolFunded {
+        pub asset_id: AssetId,
+        pub amount: Balance,
+        // The asset's pool after funding
+        pub reward_pool: Balance,
+        pub admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SponsoredClaimRecorded {
+        pub account: AccountId,
+        pub cost: Balance,
+        pub remaining: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SponsorshipExhausted {
+        pub timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyClaimed {
+        pub user: AccountId,
+        pub amount: Balance,
+        pub asset_id: AssetId,
+        pub fee_to_treasury: Balance,
+        pub fee_burned: Balance,
+        pub receipt_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct XCMExecuted {
+        pub user: AccountId,
+        pub amount: Balance,
+        pub destination: u32,
+        pub success: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum VestingError {
+        InsufficientBalance,
+        TokensStillLocked,
+        NoDepositFound,
+        UnauthorizedAccess,
+        AssetNotSupported,
+        XCMExecutionFailed,
+        InvalidBasisPoints,
+        NotAllowlisted,
+        InvalidLockPeriod,
+        DepositsPaused,
+        DepositAlreadyExists,
+        InvalidSchedule,
+        AssetStillSupported,
+        AssetStillLocked,
+        ClaimsPaused,
+        TooManyDestinations,
+        DestinationNotAllowed,
+        TogglingTooFast,
+        ConfirmationRequired,
+        BelowLargeDepositThreshold,
+        InvalidPreset,
+        TooManyPresets,
+        EmergencyModeActive,
+        TooManyAssets,
+        TransferFailed,
+        BalanceQueryFailed,
+        LockedExceedsBalance,
+        AlreadyMigrated,
+        UnknownXcmHash,
+        InvalidAssetMetadata,
+        BatchTooLarge,
+        TvlCapReached,
+        TooManyDepositors,
+        InvalidThreshold,
+        AlreadyGuardian,
+        NotGuardian,
+        RewardPoolEmpty,
+        NoRewards,
+        DestinationNotRegistered,
+        InvalidBucketSize,
+        TooManyBuckets,
+        InsufficientTreasury,
+        FeeAssetNotConfigured,
+        WouldReapAccount,
+        DeadmanNotDue,
+        InvalidNonce,
+        InvalidSignature,
+        NotEnoughApprovals,
+        ArithmeticOverflow,
+        EarlyExitDisabled,
+        UnlockHeld,
+        FeeAssetMismatch,
+        GrantNotApproved,
+    }
+
+    impl VestingVault {
+        #[ink(constructor)]
+        pub fn new(admin: AccountId) -> Self {
+            let mut supported_assets = Vec::new();
+            supported_assets.push(AssetId(1)); // DOT
+            supported_assets.push(AssetId(2)); // USDT
+
+            Self::new_with_config(admin, admin, DEFAULT_MIN_LOCK, u64::MAX, supported_assets, 0)
+                .expect("default configuration is valid")
+        }
+
+        // Deploy-time configuration so a fresh vault doesn't need a round of setters
+        #[ink(constructor)]
+        pub fn new_with_config(
+            admin: AccountId,
+            treasury: AccountId,
+            min_lock: u64,
+            max_lock: u64,
+            supported_assets: Vec<AssetId>,
+            deadman_secs: u64,
+        ) -> Result<Self, VestingError> {
+            if min_lock > max_lock {
+                return Err(VestingError::InvalidLockPeriod);
+            }
+            // Duplicates are ignored, as in add_supported_asset
+            let mut assets = Vec::new();
+            for asset_id in supported_assets {
+                if !assets.contains(&asset_id) {
+                    assets.push(asset_id);
+                }
+            }
+            if assets.len() > MAX_SUPPORTED_ASSETS {
+                return Err(VestingError::TooManyAssets);
+            }
+
+            Ok(Self {
+                deposits: Default::default(),
+                emergency_mode: false,
+                admin,
+                total_locked: 0,
+                supported_assets: assets,
+                claim_fee_bps: 0,
+                burn_bps: 0,
+                treasury_balance: 0,
+                total_burned: 0,
+                allowlist_enabled: false,
+                allowlist: Default::default(),
+                fee_buffer_bps: 0,
+                xcm_execution_fee: 0,
+                min_lock,
+                max_lock,
+                deposits_paused: false,
+                treasury,
+                schedules: Default::default(),
+                locked_by_asset: Default::default(),
+                reconciliation_tolerance: 0,
+                depositors: Default::default(),
+                depositor_positions: Default::default(),
+                depositor_count: 0,
+                claims_paused: false,
+                outflow_breaker_enabled: false,
+                outflow_threshold: 0,
+                outflow_window: 0,
+                outflow_window_start: 0,
+                outflow_in_window: 0,
+                claim_approvals: Default::default(),
+                max_destinations_per_account: DEFAULT_MAX_DESTINATIONS,
+                fee_credits: Default::default(),
+                allowed_destinations: Vec::new(),
+                emergency_toggle_cooldown: 0,
+                last_emergency_toggle: None,
+                large_deposit_threshold: None,
+                pending_deposits: Default::default(),
+                emergency_triggered_at: None,
+                emergency_triggered_by: None,
+                deposit_presets: Vec::new(),
+                lock_weights: Mapping::default(),
+                total_lock_weight: 0,
+                asset_contracts: Mapping::default(),
+                tvl_checkpoints: Mapping::default(),
+                tvl_checkpoint_count: 0,
+                deposit_fee_bps: 0,
+                verbose_events: true,
+                sponsor_cap: 0,
+                sponsor_requires_allowlist: false,
+                sponsorship_budget: 0,
+                migrated: Mapping::default(),
+                xcm_statuses: Mapping::default(),
+                asset_metadata: Mapping::default(),
+                emergency_claim_count: 0,
+                emergency_depositor_base: 0,
+                emergency_milestones_bps: Vec::new(),
+                tvl_cap: None,
+                deposits_root: None,
+                guardians: Vec::new(),
+                guardian_threshold: 0,
+                claim_receipts: Mapping::default(),
+                next_receipt_id: 0,
+                lock_starts: Mapping::default(),
+                early_exit_penalty_bps: 0,
+                reward_pool: 0,
+                reward_rate_bps: 0,
+                reward_checkpoints: Mapping::default(),
+                claim_destinations_required: false,
+                claim_destination_approval: false,
+                allowed_claim_destinations: Mapping::default(),
+                pending_claim_destinations: Mapping::default(),
+                emergency_local_payout: false,
+                fee_asset_by_destination: Mapping::default(),
+                strict_fee_assets: false,
+                total_claimed: 0,
+                claimed_by_asset: Mapping::default(),
+                asset_min_balances: Mapping::default(),
+                deadman_secs,
+                last_admin_activity: Self::env().block_timestamp(),
+                rounding: RoundingMode::Down,
+                destination_nonces: Mapping::default(),
+                pending_min_lock: None,
+                min_lock_grace_secs: 0,
+                last_claim_hashes: Mapping::default(),
+                treasury_by_asset: Mapping::default(),
+                reward_pools: Mapping::default(),
+                emergency_approvals: Vec::new(),
+                unlock_holds: Mapping::default(),
+                pending_by_asset: Mapping::default(),
+                fee_credits_by_asset: Mapping::default(),
+                migrating_by_asset: Mapping::default(),
+                unlock_approvals: Mapping::default(),
+                grant_approvals: Mapping::default(),
+                burned_by_asset: Mapping::default(),
+            })
+        }
+
+        // Asset Precompile Integration
+        #[ink(message, payable)]
+        pub fn deposit_with_asset(
+            &mut self, 
+            asset_id: AssetId, 
+            amount: Balance, 
+            lock_secs: u64,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+
+            self.validate_deposit(caller, &asset_id, amount, lock_secs, destination_parachain)?;
+            if self.is_large_deposit(amount) {
+                return Err(VestingError::ConfirmationRequired);
+            }
+
+            // In a real implementation, this would call the Assets precompile
+            // to transfer tokens from the user to the contract
+            self.call_assets_precompile_transfer(caller, amount, asset_id.clone())?;
+
+            self.open_deposit(caller, asset_id, amount, lock_secs, destination_parachain)
+        }
+
+        // Lets `grantor` fund one deposit for the caller with `grant`. An account holds a
+        // single deposit, so nobody can lock it up without the owner's consent.
+        #[ink(message)]
+        pub fn approve_grantor(&mut self, grantor: AccountId) -> Result<(), VestingError> {
+            let recipient = self.env().caller();
+            self.grant_approvals.insert(recipient, &grantor);
+            Ok(())
+        }
+
+        // Funds a deposit owned by `recipient`: the caller pays, only the recipient can claim.
+        // The recipient must have approved the caller with approve_grantor first.
+        #[ink(message, payable)]
+        pub fn grant(
+            &mut self,
+            recipient: AccountId,
+            asset_id: AssetId,
+            amount: Balance,
+            lock_secs: u64,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            if self.grant_approvals.get(recipient) != Some(caller) {
+                return Err(VestingError::GrantNotApproved);
+            }
+
+            self.ensure_deposits_open(caller)?;
+            self.validate_deposit(recipient, &asset_id, amount, lock_secs, destination_parachain)?;
+            if self.is_large_deposit(amount) {
+                return Err(VestingError::ConfirmationRequired);
+            }
+
+            self.call_assets_precompile_transfer(caller, amount, asset_id.clone())?;
+            self.grant_approvals.remove(recipient);
+            self.open_deposit(recipient, asset_id.clone(), amount, lock_secs, destination_parachain)?;
+
+            let info = self.deposits.get(recipient).ok_or(VestingError::NoDepositFound)?;
+            self.env().emit_event(Granted {
+                user: recipient,
+                granted_by: caller,
+                amount: info.amount,
+                asset_id,
+                unlock_time: info.unlock_timestamp,
+            });
+
+            Ok(())
+        }
+
+        // Deposit on one of the admin-approved product terms
+        #[ink(message, payable)]
+        pub fn deposit_preset(
+            &mut self,
+            preset_index: u32,
+            amount: Balance,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            let (asset_id, lock_secs) = self
+                .deposit_presets
+                .get(preset_index as usize)
+                .cloned()
+                .ok_or(VestingError::InvalidPreset)?;
+            self.deposit_with_asset(asset_id, amount, lock_secs, destination_parachain)
+        }
+
+        // Large deposits are escrowed first and only become a deposit once confirmed
+        #[ink(message, payable)]
+        pub fn initiate_large_deposit(
+            &mut self,
+            asset_id: AssetId,
+            amount: Balance,
+            lock_secs: u64,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+
+            self.validate_deposit(caller, &asset_id, amount, lock_secs, destination_parachain)?;
+            if !self.is_large_deposit(amount) {
+                return Err(VestingError::BelowLargeDepositThreshold);
+            }
+            if self.pending_deposits.contains(caller) {
+                return Err(VestingError::DepositAlreadyExists);
+            }
+
+            self.call_assets_precompile_transfer(caller, amount, asset_id.clone())?;
+
+            let pending = PendingDeposit {
+                asset_id: asset_id.clone(),
+                amount,
+                lock_secs,
+                destination_parachain,
+                created_at: self.env().block_timestamp(),
+            };
+            self.pending_deposits.insert(caller, &pending);
+            let escrowed = self.pending_by_asset.get(&asset_id).unwrap_or(0) + amount;
+            self.pending_by_asset.insert(&asset_id, &escrowed);
+
+            self.env().emit_event(DepositPending {
+                user: caller,
+                amount,
+                asset_id,
+                lock_secs,
+            });
+
+            Ok(())
+        }
+
+        // The lock starts at confirmation, not at initiation
+        #[ink(message)]
+        pub fn confirm_deposit(&mut self) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            let pending = self.pending_deposits.get(caller)
+                .ok_or(VestingError::NoDepositFound)?;
+
+            // Conditions may have changed since initiation; the user can still cancel
+            self.validate_deposit(
+                caller,
+                &pending.asset_id,
+                pending.amount,
+                pending.lock_secs,
+                pending.destination_parachain,
+            )?;
+
+            self.pending_deposits.remove(caller);
+            self.release_escrow(&pending.asset_id, pending.amount);
+            self.open_deposit(
+                caller,
+                pending.asset_id.clone(),
+                pending.amount,
+                pending.lock_secs,
+                pending.destination_parachain,
+            )?;
+
+            self.env().emit_event(DepositConfirmed {
+                user: caller,
+                amount: pending.amount,
+                asset_id: pending.asset_id,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cancel_deposit(&mut self) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            let pending = self.pending_deposits.take(caller)
+                .ok_or(VestingError::NoDepositFound)?;
+            self.release_escrow(&pending.asset_id, pending.amount);
+
+            self.pay_out(caller, pending.amount, pending.asset_id.clone())?;
+
+            self.env().emit_event(DepositCancelled {
+                user: caller,
+                amount: pending.amount,
+                asset_id: pending.asset_id,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_large_deposit_threshold(&mut self, threshold: Option<Balance>) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.large_deposit_threshold = threshold;
+            Ok(())
+        }
+
+        fn release_escrow(&mut self, asset_id: &AssetId, amount: Balance) {
+            let escrowed = self.pending_by_asset.get(asset_id).unwrap_or(0).saturating_sub(amount);
+            self.pending_by_asset.insert(asset_id, &escrowed);
+        }
+
+        fn is_large_deposit(&self, amount: Balance) -> bool {
+            self.large_deposit_threshold.map_or(false, |threshold| amount > threshold)
+        }
+
+        fn validate_deposit(
+            &self,
+            caller: AccountId,
+            asset_id: &AssetId,
+            amount: Balance,
+            lock_secs: u64,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            self.ensure_deposits_open(caller)?;
+            self.ensure_supported_asset(asset_id)?;
+
+            // A second deposit would overwrite the first and orphan its funds
+            if self.deposits.contains(caller) {
+                return Err(VestingError::DepositAlreadyExists);
+            }
+
+            self.ensure_valid_lock(lock_secs)?;
+            self.ensure_destination_allowed(destination_parachain)?;
+            self.ensure_destination_capacity(caller, destination_parachain)?;
+            assert!(amount > 0, "Amount must be greater than zero");
+
+            // The deposit fee and fee buffer must leave some claimable principal
+            let locked = amount - self.bps_of(amount, self.deposit_fee_bps);
+            if locked - self.bps_of(locked, self.fee_buffer_bps) == 0 {
+                return Err(VestingError::InsufficientBalance);
+            }
+
+            self.ensure_under_tvl_cap(locked)
+        }
+
+        fn ensure_under_tvl_cap(&self, added: Balance) -> Result<(), VestingError> {
+            match self.tvl_cap {
+                Some(cap) if self.total_locked.saturating_add(added) > cap => Err(VestingError::TvlCapReached),
+                _ => Ok(()),
+            }
+        }
+
+        // Records a validated deposit whose funds are already held by the vault
+        fn open_deposit(
+            &mut self,
+            owner: AccountId,
+            asset_id: AssetId,
+            amount: Balance,
+            lock_secs: u64,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            let unlock_time = self.env().block_timestamp() + lock_secs;
+
+            // The entry fee goes to the treasury and is never locked
+            let deposit_fee = self.bps_of(amount, self.deposit_fee_bps);
+            self.credit_treasury(&asset_id, deposit_fee);
+            let locked = amount - deposit_fee;
+
+            // Part of the deposit is held back to pay for destination execution at claim
+            let reserved_fee = self.bps_of(locked, self.fee_buffer_bps);
+            let principal = locked - reserved_fee;
+
+            let info = DepositInfo {
+                amount: principal,
+                unlock_timestamp: unlock_time,
+                asset_id: asset_id.clone(),
+                destination_parachain,
+                reserved_fee,
+                destination_account: *owner.as_ref(),
+            };
+
+            self.deposits.insert(owner, &info);
+            self.deposits_root = None;
+            self.index_depositor(owner);
+            self.increase_locked(&info.asset_id, locked);
+            self.set_lock_weight(owner, principal.saturating_mul(lock_secs as u128))?;
+            self.lock_starts.insert(owner, &self.env().block_timestamp());
+            
+            self.env().emit_event(Deposited {
+                user: owner,
+                amount: principal,
+                asset_id,
+                unlock_time,
+                reserved_fee,
+                gross: amount,
+                deposit_fee,
+            });
+
+            self.ensure_backed(&info.asset_id)
+        }
+
+        // XCM Cross-Chain Claim. `override_destination` sends this claim to another
+        // (parachain, account) instead of the one stored with the deposit; it must be
+        // an allowed destination and, when registration is required, a registered one.
+        #[ink(message)]
+        pub fn claim_cross_chain(
+            &mut self,
+            override_destination: Option<(u32, [u8; 32])>,
+        ) -> Result<u64, VestingError> {
+            let caller = self.env().caller();
+            self.claim_deposit(caller, false, override_destination, None)
+        }
+
+        // Claims before the unlock time, paying the pro-rated early exit penalty to
+        // the treasury on top of the usual claim fee. Disabled while the penalty is 0.
+        #[ink(message)]
+        pub fn early_withdraw(&mut self) -> Result<u64, VestingError> {
+            let caller = self.env().caller();
+            self.claim_deposit(caller, true, None, None)
+        }
+
+        // Delegated claim: an approved operator pays for the call, funds still go to the owner's destination
+        #[ink(message)]
+        pub fn claim_for(&mut self, owner: AccountId) -> Result<u64, VestingError> {
+            if self.claim_approvals.get(owner) != Some(self.env().caller()) {
+                return Err(VestingError::UnauthorizedAccess);
+            }
+            self.claim_deposit(owner, false, None, None)
+        }
+
+        #[ink(message)]
+        pub fn approve_claimer(&mut self, operator: AccountId) -> Result<(), VestingError> {
+            let owner = self.env().caller();
+            self.claim_approvals.insert(owner, &operator);
+            self.env().emit_event(ClaimerApproved { owner, operator });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn revoke_claimer(&mut self) -> Result<(), VestingError> {
+            let owner = self.env().caller();
+            let operator = self.claim_approvals.take(owner)
+                .ok_or(VestingError::UnauthorizedAccess)?;
+            self.env().emit_event(ClaimerRevoked { owner, operator });
+            Ok(())
+        }
+
+        // Returns the id of the claim's receipt. `tranches` folds matured schedule
+        // tranches (from claimable_schedule) into the same transfer; the caller must
+        // make sure they share the deposit's asset and destination.
+        fn claim_deposit(
+            &mut self,
+            owner: AccountId,
+            early: bool,
+            destination: Option<(u32, [u8; 32])>,
+            tranches: Option<(VestingSchedule, Tranche)>,
+        ) -> Result<u64, VestingError> {
+            let current_time = self.env().block_timestamp();
+            let stored = self.claimable_deposit(owner, early, destination)?;
+            // Fees, credit and the XCM all follow the destination this claim goes to
+            let mut info = stored.clone();
+            if let Some((parachain, account)) = destination {
+                info.destination_parachain = parachain;
+                info.destination_account = account;
+            }
+            if let Some((_, matured)) = &tranches {
+                info.amount += matured.amount;
+                info.reserved_fee += matured.reserved_fee;
+            }
+            let local = self.pays_out_locally();
+            let preview = self.quote_claim(owner, &info);
+            let (fee_to_treasury, fee_burned) = self.claim_fee_split(info.amount);
+            self.consume_fee_credit(owner, info.destination_parachain, preview.fee_credit_used);
+            let (penalty_bps, penalty) = if early {
+                let (bps, penalty) = self.early_exit_penalty(owner, &info);
+                (bps, penalty.min(preview.net))
+            } else {
+                (0, 0)
+            };
+            let net_amount = preview.net - penalty;
+
+            // Execute XCM cross-chain transfer; local payouts have no message and a zero hash
+            let xcm_hash = if local {
+                self.pay_out(owner, net_amount, info.asset_id.clone())?;
+                [0u8; 32]
+            } else {
+                self.execute_xcm_transfer(
+                    AccountId::from(info.destination_account),
+                    net_amount,
+                    preview.execution_fee,
+                    info.destination_parachain,
+                    info.asset_id.clone(),
+                )?
+            };
+
+            self.decrease_locked(&info.asset_id, info.amount + info.reserved_fee);
+            self.collect_claim_fee(&info.asset_id, fee_to_treasury, fee_burned);
+            self.credit_treasury(&info.asset_id, penalty);
+            self.record_outflow(info.amount + info.reserved_fee);
+            self.record_emergency_claim();
+            self.deposits.remove(owner);
+            self.deposits_root = None;
+            self.set_lock_weight(owner, 0)?;
+            self.lock_starts.remove(owner);
+            self.unlock_holds.remove(owner);
+            self.unlock_approvals.remove(owner);
+            self.reward_checkpoints.remove(owner);
+            if let Some((schedule, _)) = tranches {
+                self.remove_matured_tranches(owner, schedule);
+            }
+            self.unindex_if_closed(owner);
+            self.ensure_backed(&info.asset_id)?;
+            if !local {
+                self.last_claim_hashes.insert(owner, &xcm_hash);
+            }
+
+            let receipt_id = self.record_receipt(ClaimReceipt {
+                owner,
+                gross: preview.gross,
+                fee: preview.fee + penalty,
+                execution_fee: preview.execution_fee,
+                net: net_amount,
+                asset_id: info.asset_id.clone(),
+                destination_parachain: info.destination_parachain,
+                xcm_hash,
+                timestamp: current_time,
+            });
+
+            if early {
+                self.env().emit_event(EarlyWithdrawn {
+                    user: owner,
+                    penalty_bps,
+                    penalty,
+                });
+            }
+
+            if local {
+                self.env().emit_event(EmergencyClaimed {
+                    user: owner,
+                    amount: net_amount,
+                    asset_id: info.asset_id,
+                    fee_to_treasury,
+                    fee_burned,
+                    receipt_id,
+                });
+            } else {
+                self.env().emit_event(ClaimInitiated {
+                    user: owner,
+                    amount: net_amount,
+                    destination_parachain: info.destination_parachain,
+                    xcm_hash,
+                    fee_to_treasury,
+                    fee_burned,
+                    receipt_id,
+                    asset_id: info.asset_id,
+                    destination_account: info.destination_account,
+                    stored_destination_parachain: stored.destination_parachain,
+                    stored_destination_account: stored.destination_account,
+                });
+            }
+
+            Ok(receipt_id)
+        }
+
+        // Every check a deposit claim must pass before anything moves. effective_status
+        // and deposit_state run the same function, so they can't disagree. Returns the
+        // deposit as stored; `destination` is only validated here.
+        fn claimable_deposit(
+            &self,
+            owner: AccountId,
+            early: bool,
+            destination: Option<(u32, [u8; 32])>,
+        ) -> Result<DepositInfo, VestingError> {
+            let info = match self.deposits.get(owner) {
+                Some(info) => info,
+                None if self.migrated.contains(owner) => return Err(VestingError::AlreadyMigrated),
+                None => return Err(VestingError::NoDepositFound),
+            };
+
+            self.ensure_claims_open()?;
+
+            if self.env().block_timestamp() < info.unlock_timestamp && !self.emergency_mode {
+                if !early {
+                    return Err(VestingError::TokensStillLocked);
+                }
+                // Early exit stays off until the admin sets a penalty, and never
+                // overrides an unlock the admin imposed
+                if self.early_exit_penalty_bps == 0 {
+                    return Err(VestingError::EarlyExitDisabled);
+                }
+                if self.unlock_holds.contains(owner) {
+                    return Err(VestingError::UnlockHeld);
+                }
+            }
+            if let Some((parachain, _)) = destination {
+                self.ensure_destination_listed(parachain)?;
+            }
+            if !self.pays_out_locally() {
+                let (parachain, account) = destination
+                    .unwrap_or((info.destination_parachain, info.destination_account));
+                self.ensure_destination_registered(owner, parachain, account)?;
+                self.fee_asset_for(parachain, &info.asset_id)?;
+            }
+            Ok(info)
+        }
+
+        fn pays_out_locally(&self) -> bool {
+            self.emergency_mode && self.emergency_local_payout
+        }
+
+        // Every claim path ends here, so the lifetime totals are kept in step with receipts
+        fn record_receipt(&mut self, receipt: ClaimReceipt) -> u64 {
+            self.total_claimed = self.total_claimed.saturating_add(receipt.net);
+            let claimed = self.claimed_by_asset.get(&receipt.asset_id).unwrap_or(0);
+            self.claimed_by_asset.insert(&receipt.asset_id, &claimed.saturating_add(receipt.net));
+
+            let receipt_id = self.next_receipt_id;
+            self.claim_receipts.insert(receipt_id, &receipt);
+            self.next_receipt_id += 1;
+            receipt_id
+        }
+
+        // Multi-tranche grant: each (amount, unlock) pair vests independently
+        #[ink(message, payable)]
+        pub fn deposit_schedule(
+            &mut self,
+            asset_id: AssetId,
+            total_amount: Balance,
+            tranches: Vec<(Balance, Timestamp)>,
+            destination_parachain: u32,
+        ) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            self.ensure_deposits_open(caller)?;
+            self.ensure_supported_asset(&asset_id)?;
+
+            if self.schedules.contains(caller) {
+                return Err(VestingError::DepositAlreadyExists);
+            }
+
+            if tranches.is_empty() || tranches.len() > MAX_TRANCHES {
+                return Err(VestingError::InvalidSchedule);
+            }
+
+            self.ensure_destination_allowed(destination_parachain)?;
+            self.ensure_destination_capacity(caller, destination_parachain)?;
+
+            // Each tranche pays the deposit fee and reserves the fee buffer exactly as
+            // a deposit of its size would
+            let mut sum: Balance = 0;
+            let mut deposit_fee: Balance = 0;
+            let mut schedule_tranches = Vec::new();
+            for (amount, unlock_timestamp) in tranches {
+                if amount == 0 || unlock_timestamp <= current_time {
+                    return Err(VestingError::InvalidSchedule);
+                }
+                self.ensure_valid_lock(unlock_timestamp - current_time)?;
+                sum = sum.checked_add(amount).ok_or(VestingError::InvalidSchedule)?;
+
+                let fee = self.bps_of(amount, self.deposit_fee_bps);
+                let locked = amount - fee;
+                let reserved_fee = self.bps_of(locked, self.fee_buffer_bps);
+                if locked - reserved_fee == 0 {
+                    return Err(VestingError::InsufficientBalance);
+                }
+                deposit_fee += fee;
+                schedule_tranches.push(Tranche {
+                    amount: locked - reserved_fee,
+                    unlock_timestamp,
+                    reserved_fee,
+                });
+            }
+            if sum != total_amount {
+                return Err(VestingError::InvalidSchedule);
+            }
+            // Schedules have no confirmation step, so large ones are refused outright
+            if self.is_large_deposit(total_amount) {
+                return Err(VestingError::ConfirmationRequired);
+            }
+            let locked = total_amount - deposit_fee;
+            self.ensure_under_tvl_cap(locked)?;
+
+            self.call_assets_precompile_transfer(caller, total_amount, asset_id.clone())?;
+
+            let schedule = VestingSchedule {
+                asset_id: asset_id.clone(),
+                destination_parachain,
+                tranches: schedule_tranches,
+            };
+            let tranche_count = schedule.tranches.len() as u32;
+
+            self.schedules.insert(caller, &schedule);
+            self.index_depositor(caller);
+            self.credit_treasury(&schedule.asset_id, deposit_fee);
+            self.increase_locked(&schedule.asset_id, locked);
+            self.ensure_backed(&schedule.asset_id)?;
+
+            self.env().emit_event(ScheduleDeposited {
+                user: caller,
+                total_amount,
+                asset_id,
+                tranche_count,
+            });
+
+            Ok(())
+        }
+
+        // Releases only the tranches whose unlock has passed
+        #[ink(message)]
+        pub fn claim_schedule(&mut self) -> Result<u64, VestingError> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            let (schedule, matured) = self.claimable_schedule(caller)?;
+            let preview = self.quote_schedule_claim(caller, &schedule, &matured);
+            let amount = matured.amount;
+            let released = matured.amount + matured.reserved_fee;
+            let local = self.pays_out_locally();
+
+            let (fee_to_treasury, fee_burned) = self.claim_fee_split(amount);
+            let net_amount = preview.net;
+            let execution_fee = preview.execution_fee;
+
+            let xcm_hash = if local {
+                self.pay_out(caller, net_amount, schedule.asset_id.clone())?;
+                [0u8; 32]
+            } else {
+                self.consume_fee_credit(caller, schedule.destination_parachain, preview.fee_credit_used);
+                self.execute_xcm_transfer(
+                    caller,
+                    net_amount,
+                    execution_fee,
+                    schedule.destination_parachain,
+                    schedule.asset_id.clone(),
+                )?
+            };
+
+            self.decrease_locked(&schedule.asset_id, released);
+            self.collect_claim_fee(&schedule.asset_id, fee_to_treasury, fee_burned);
+            self.record_outflow(released);
+            self.record_emergency_claim();
+            self.remove_matured_tranches(caller, schedule.clone());
+            self.ensure_backed(&schedule.asset_id)?;
+
+            let receipt_id = self.record_receipt(ClaimReceipt {
+                owner: caller,
+                gross: amount,
+                fee: fee_to_treasury + fee_burned,
+                execution_fee,
+                net: net_amount,
+                asset_id: schedule.asset_id.clone(),
+                destination_parachain: schedule.destination_parachain,
+                xcm_hash,
+                timestamp: current_time,
+            });
+
+            if local {
+                self.env().emit_event(EmergencyClaimed {
+                    user: caller,
+                    amount: net_amount,
+                    asset_id: schedule.asset_id,
+                    fee_to_treasury,
+                    fee_burned,
+                    receipt_id,
+                });
+            } else {
+                self.env().emit_event(ClaimInitiated {
+                    user: caller,
+                    amount: net_amount,
+                    destination_parachain: schedule.destination_parachain,
+                    xcm_hash,
+                    fee_to_treasury,
+                    fee_burned,
+                    receipt_id,
+                    asset_id: schedule.asset_id,
+                    destination_account: *caller.as_ref(),
+                    stored_destination_parachain: schedule.destination_parachain,
+                    stored_destination_account: *caller.as_ref(),
+                });
+            }
+
+            Ok(receipt_id)
+        }
+
+        // Drops the tranches claimable now, closing the schedule once none are left
+        fn remove_matured_tranches(&mut self, owner: AccountId, mut schedule: VestingSchedule) {
+            let now = self.env().block_timestamp();
+            let emergency_mode = self.emergency_mode;
+            schedule.tranches.retain(|t| !emergency_mode && t.unlock_timestamp > now);
+            if schedule.tranches.is_empty() {
+                self.schedules.remove(owner);
+                self.unindex_if_closed(owner);
+            } else {
+                self.schedules.insert(owner, &schedule);
+            }
+        }
+
+        // Every check claim_schedule must pass before anything moves, shared with
+        // total_claimable. Returns the schedule and its matured tranches summed into one.
+        fn claimable_schedule(&self, owner: AccountId) -> Result<(VestingSchedule, Tranche), VestingError> {
+            self.ensure_claims_open()?;
+
+            let schedule = self.schedules.get(owner)
+                .ok_or(VestingError::NoDepositFound)?;
+
+            let now = self.env().block_timestamp();
+            let mut matured = Tranche { amount: 0, unlock_timestamp: now, reserved_fee: 0 };
+            for tranche in schedule
+                .tranches
+                .iter()
+                .filter(|t| self.emergency_mode || t.unlock_timestamp <= now)
+            {
+                matured.amount += tranche.amount;
+                matured.reserved_fee += tranche.reserved_fee;
+            }
+            if matured.amount == 0 {
+                return Err(VestingError::TokensStillLocked);
+            }
+            if !self.pays_out_locally() {
+                self.ensure_destination_registered(owner, schedule.destination_parachain, *owner.as_ref())?;
+                self.fee_asset_for(schedule.destination_parachain, &schedule.asset_id)?;
+            }
+            Ok((schedule, matured))
+        }
+
+        // quote_claim for the matured part of a schedule, which always pays out to the
+        // owner's own account on the schedule's destination
+        fn quote_schedule_claim(&self, owner: AccountId, schedule: &VestingSchedule, matured: &Tranche) -> ClaimPreview {
+            self.quote_claim(owner, &DepositInfo {
+                amount: matured.amount,
+                unlock_timestamp: matured.unlock_timestamp,
+                asset_id: schedule.asset_id.clone(),
+                destination_parachain: schedule.destination_parachain,
+                reserved_fee: matured.reserved_fee,
+                destination_account: *owner.as_ref(),
+            })
+        }
+
+        // Sweeps everything the caller can claim without a penalty: the deposit once
+        // unlocked and all matured schedule tranches; unmatured tranches stay in place.
+        // When the deposit and the tranches are the same asset bound for the same account
+        // on the same chain, they go out as one transfer paying one execution fee.
+        // Returns the receipt ids, one ClaimInitiated each.
+        #[ink(message)]
+        pub fn claim_all_matured(&mut self) -> Result<Vec<u64>, VestingError> {
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+            let emergency_mode = self.emergency_mode;
+
+            let deposit = self.deposits.get(caller);
+            let schedule = self.schedules.get(caller);
+            if deposit.is_none() && schedule.is_none() {
+                return Err(VestingError::NoDepositFound);
+            }
+            let deposit_due = deposit
+                .as_ref()
+                .is_some_and(|info| emergency_mode || info.unlock_timestamp <= now);
+            let schedule_due = schedule.as_ref().is_some_and(|schedule| {
+                schedule.tranches.iter().any(|t| emergency_mode || t.unlock_timestamp <= now)
+            });
+
+            if let (true, true, Some(info)) = (deposit_due, schedule_due, &deposit) {
+                if let Ok((schedule, matured)) = self.claimable_schedule(caller) {
+                    let same_route = self.pays_out_locally()
+                        || (info.destination_parachain == schedule.destination_parachain
+                            && AccountId::from(info.destination_account) == caller);
+                    if info.asset_id == schedule.asset_id && same_route {
+                        return Ok(vec![self.claim_deposit(caller, false, None, Some((schedule, matured)))?]);
+                    }
+                }
+            }
+
+            let mut receipts = Vec::new();
+            if deposit_due {
+                receipts.push(self.claim_deposit(caller, false, None, None)?);
+            }
+            if schedule_due {
+                receipts.push(self.claim_schedule()?);
+            }
+
+            if receipts.is_empty() {
+                return Err(VestingError::TokensStillLocked);
+            }
+            Ok(receipts)
+        }
+
+        // Pays destination execution fees for later claims ahead of time. The credit only
+        // covers claims whose fee asset is `asset_id`, so it must match the destination's
+        // configured fee asset and any credit already held for it.
+        #[ink(message, payable)]
+        pub fn prefund_destination_fee(
+            &mut self,
+            asset_id: AssetId,
+            amount: Balance,
+            destination: u32,
+        ) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            self.ensure_supported_asset(&asset_id)?;
+            if amount == 0 {
+                return Err(VestingError::InsufficientBalance);
+            }
+
+            if self
+                .fee_asset_by_destination
+                .get(destination)
+                .is_some_and(|fee_asset| fee_asset != asset_id)
+            {
+                return Err(VestingError::FeeAssetMismatch);
+            }
+            let (credit_asset, credit) = self
+                .fee_credits
+                .get((caller, destination))
+                .unwrap_or((asset_id.clone(), 0));
+            if credit_asset != asset_id {
+                return Err(VestingError::FeeAssetMismatch);
+            }
+
+            self.call_assets_precompile_transfer(caller, amount, asset_id.clone())?;
+
+            self.fee_credits.insert((caller, destination), &(asset_id.clone(), credit + amount));
+            let credits = self.fee_credits_by_asset.get(&asset_id).unwrap_or(0) + amount;
+            self.fee_credits_by_asset.insert(&asset_id, &credits);
+
+            self.env().emit_event(FeePrefunded {
+                user: caller,
+                asset_id,
+                amount,
+                destination,
+            });
+
+            Ok(())
+        }
+
+        // Circuit Breaker - Emergency Withdraw
+        #[ink(message)]
+        pub fn emergency_unlock(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if (self.emergency_approvals.len() as u32) < self.guardian_threshold {
+                return Err(VestingError::NotEnoughApprovals);
+            }
+            self.record_emergency_toggle()?;
+            self.emergency_approvals.clear();
+
+            let timestamp = self.env().block_timestamp();
+            self.emergency_mode = true;
+            self.emergency_triggered_at = Some(timestamp);
+            self.emergency_triggered_by = Some(self.admin);
+            self.emergency_claim_count = 0;
+            self.emergency_depositor_base = self.depositor_count;
+
+            self.env().emit_event(EmergencyTriggered {
+                timestamp,
                 admin: self.admin,
             });
 
-            Ok(())
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn resume_operations(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.record_emergency_toggle()?;
+            // Otherwise a vault resumed after a dead-man trigger could be tripped again at once
+            self.touch_admin_activity();
+
+            self.emergency_mode = false;
+            self.emergency_claim_count = 0;
+
+            self.env().emit_event(OperationsResumed {
+                timestamp: self.env().block_timestamp(),
+                admin: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        // Liveness probe for monitoring: a signed call that only emits Heartbeat, so a
+        // failure to land one points at signing, submission or the chain itself
+        #[ink(message)]
+        pub fn heartbeat(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.env().emit_event(Heartbeat {
+                timestamp: self.env().block_timestamp(),
+                total_locked: self.total_locked,
+            });
+            Ok(())
+        }
+
+        // Records locked_by_asset for all supported assets in one event, for time series
+        // built from events. At most MAX_SUPPORTED_ASSETS entries.
+        #[ink(message)]
+        pub fn snapshot_assets(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            let entries = self
+                .supported_assets
+                .iter()
+                .map(|asset_id| (asset_id.clone(), self.locked_by_asset.get(asset_id).unwrap_or(0)))
+                .collect();
+            self.env().emit_event(AssetSnapshot {
+                entries,
+                timestamp: self.env().block_timestamp(),
+            });
+            Ok(())
+        }
+
+        // Proves the admin key is still held, pushing back the dead-man's switch
+        #[ink(message)]
+        pub fn heartbeat_admin(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.touch_admin_activity();
+            Ok(())
+        }
+
+        // Callable by anyone once the admin has been silent for deadman_secs, so funds
+        // can be recovered if the admin key is lost. Ignores the toggle cooldown, which
+        // only exists to slow down the admin.
+        #[ink(message)]
+        pub fn trigger_deadman(&mut self) -> Result<(), VestingError> {
+            let now = self.env().block_timestamp();
+            if self.deadman_secs == 0
+                || now.saturating_sub(self.last_admin_activity) < self.deadman_secs
+            {
+                return Err(VestingError::DeadmanNotDue);
+            }
+            if self.emergency_mode {
+                return Ok(());
+            }
+
+            let caller = self.env().caller();
+            self.emergency_mode = true;
+            self.emergency_triggered_at = Some(now);
+            self.emergency_triggered_by = Some(caller);
+            self.emergency_claim_count = 0;
+            self.emergency_depositor_base = self.depositor_count;
+            self.last_emergency_toggle = Some(now);
+
+            self.env().emit_event(DeadmanTriggered {
+                timestamp: now,
+                caller,
+                last_admin_activity: self.last_admin_activity,
+            });
+
+            Ok(())
+        }
+
+        // (deadman_secs, last_admin_activity)
+        #[ink(message)]
+        pub fn get_deadman(&self) -> (u64, Timestamp) {
+            (self.deadman_secs, self.last_admin_activity)
+        }
+
+        fn touch_admin_activity(&mut self) {
+            self.last_admin_activity = self.env().block_timestamp();
+        }
+
+        // Minimum spacing between emergency state changes, so a briefly
+        // compromised admin can't flip the vault back and forth
+        #[ink(message)]
+        pub fn set_emergency_toggle_cooldown(&mut self, cooldown_secs: u64) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.emergency_toggle_cooldown = cooldown_secs;
+            Ok(())
+        }
+
+        // Milestones in bps of the depositors present when emergency mode was triggered
+        #[ink(message)]
+        pub fn set_emergency_milestones(&mut self, milestones_bps: Vec<u16>) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if milestones_bps.len() > MAX_EMERGENCY_MILESTONES {
+                return Err(VestingError::BatchTooLarge);
+            }
+            for bps in &milestones_bps {
+                Self::ensure_valid_bps(*bps)?;
+            }
+            self.emergency_milestones_bps = milestones_bps;
+            Ok(())
+        }
+
+        fn record_emergency_claim(&mut self) {
+            if !self.emergency_mode {
+                return;
+            }
+            let before = self.emergency_claim_count;
+            self.emergency_claim_count += 1;
+
+            let base = self.emergency_depositor_base;
+            if base == 0 {
+                return;
+            }
+            let share = |claims: u32| (claims as u64 * MAX_BPS as u64 / base as u64) as u16;
+            let (old_share, new_share) = (share(before), share(self.emergency_claim_count));
+            for milestone_bps in self.emergency_milestones_bps.clone() {
+                if old_share < milestone_bps && milestone_bps <= new_share {
+                    self.env().emit_event(EmergencyClaimMilestone {
+                        milestone_bps,
+                        claims: self.emergency_claim_count,
+                        depositors: base,
+                    });
+                }
+            }
+        }
+
+        fn record_emergency_toggle(&mut self) -> Result<(), VestingError> {
+            let now = self.env().block_timestamp();
+            if let Some(last) = self.last_emergency_toggle {
+                if now.saturating_sub(last) < self.emergency_toggle_cooldown {
+                    return Err(VestingError::TogglingTooFast);
+                }
+            }
+            self.last_emergency_toggle = Some(now);
+            Ok(())
+        }
+
+        // Fee Configuration
+        #[ink(message)]
+        pub fn set_claim_fee_bps(&mut self, bps: u16) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            Self::ensure_valid_bps(bps)?;
+            self.claim_fee_bps = bps;
+            Ok(())
+        }
+
+        // Turning verbose events off drops only the secondary XCMExecuted and TvlCheckpoint
+        // events, whose data is also in ClaimInitiated and total_locked_history. Every
+        // event that records a state change (deposits, claims, admin and emergency
+        // actions) is always emitted so the vault stays auditable.
+        #[ink(message)]
+        pub fn set_verbose_events(&mut self, enabled: bool) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.verbose_events = enabled;
+            Ok(())
+        }
+
+        // Applies to every bps amount (fees, fee buffer, burn share, penalties) and to
+        // reward accrual; quotes and previews follow the same mode
+        #[ink(message)]
+        pub fn set_rounding_mode(&mut self, mode: RoundingMode) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.rounding = mode;
+            Ok(())
+        }
+
+        // Entry fee taken from each deposit into the treasury, capped at 10%
+        #[ink(message)]
+        pub fn set_deposit_fee_bps(&mut self, bps: u16) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if bps > MAX_DEPOSIT_FEE_BPS {
+                return Err(VestingError::InvalidBasisPoints);
+            }
+            self.deposit_fee_bps = bps;
+            Ok(())
+        }
+
+        // Portion of each claim fee that is burned instead of kept by the treasury
+        #[ink(message)]
+        pub fn set_burn_bps(&mut self, bps: u16) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            Self::ensure_valid_bps(bps)?;
+            self.burn_bps = bps;
+            Ok(())
+        }
+
+        // Allowlist (private beta) - deposits are permissionless while disabled
+        #[ink(message)]
+        pub fn set_allowlist_enabled(&mut self, enabled: bool) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.allowlist_enabled = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn add_to_allowlist(&mut self, account: AccountId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.allowlist.insert(account, &());
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn remove_from_allowlist(&mut self, account: AccountId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.allowlist.remove(account);
+            Ok(())
+        }
+
+        // Destination fee buffer kept back from every deposit
+        #[ink(message)]
+        pub fn set_fee_buffer_bps(&mut self, bps: u16) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            // A full buffer would leave no claimable principal
+            if bps >= MAX_BPS {
+                return Err(VestingError::InvalidBasisPoints);
+            }
+            self.fee_buffer_bps = bps;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_xcm_execution_fee(&mut self, fee: Balance) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.xcm_execution_fee = fee;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_lock_bounds(&mut self, min_lock: u64, max_lock: u64) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if min_lock > max_lock {
+                return Err(VestingError::InvalidLockPeriod);
+            }
+            self.max_lock = max_lock;
+            self.apply_min_lock(min_lock);
+            Ok(())
+        }
+
+        // Delay before a raised min_lock takes effect; 0 applies it immediately
+        #[ink(message)]
+        pub fn set_min_lock_grace(&mut self, grace_secs: u64) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.min_lock_grace_secs = grace_secs;
+            Ok(())
+        }
+
+        // Stores a pending min_lock whose grace window has passed. Anyone may call it;
+        // deposits already enforce the new value either way.
+        #[ink(message)]
+        pub fn activate_min_lock(&mut self) {
+            self.settle_min_lock();
+        }
+
+        // (min_lock in force now, pending raise as (value, effective_at))
+        #[ink(message)]
+        pub fn get_min_lock_schedule(&self) -> (u64, Option<(u64, Timestamp)>) {
+            let current = self.effective_min_lock();
+            let pending = self.pending_min_lock.filter(|(_, effective_at)| self.env().block_timestamp() < *effective_at);
+            (current, pending)
+        }
+
+        fn effective_min_lock(&self) -> u64 {
+            match self.pending_min_lock {
+                Some((pending, effective_at)) if self.env().block_timestamp() >= effective_at => pending,
+                _ => self.min_lock,
+            }
+        }
+
+        // The min_lock deposits will face once any pending raise is in force
+        fn scheduled_min_lock(&self) -> u64 {
+            self.pending_min_lock.map_or(self.min_lock, |(pending, _)| pending)
+        }
+
+        fn settle_min_lock(&mut self) {
+            if let Some((pending, effective_at)) = self.pending_min_lock {
+                if self.env().block_timestamp() >= effective_at {
+                    self.min_lock = pending;
+                    self.pending_min_lock = None;
+                    self.env().emit_event(MinLockActivated {
+                        min_lock: pending,
+                        effective_at,
+                    });
+                }
+            }
+        }
+
+        // Raising is scheduled behind the grace window; lowering applies at once and
+        // drops any pending raise
+        fn apply_min_lock(&mut self, min_lock: u64) {
+            self.settle_min_lock();
+            if min_lock == self.scheduled_min_lock() {
+                return;
+            }
+            if min_lock > self.min_lock && self.min_lock_grace_secs > 0 {
+                let effective_at = self.env().block_timestamp().saturating_add(self.min_lock_grace_secs);
+                self.pending_min_lock = Some((min_lock, effective_at));
+                self.env().emit_event(MinLockScheduled {
+                    current: self.min_lock,
+                    pending: min_lock,
+                    effective_at,
+                });
+            } else {
+                self.min_lock = min_lock;
+                self.pending_min_lock = None;
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.treasury = treasury;
+            Ok(())
+        }
+
+        // Applies several tunables at once. The patch is validated against the values it
+        // would produce (e.g. a new min_lock against the current max_lock) and rejected
+        // as a whole if any check fails, so no inconsistent state is ever stored.
+        #[ink(message)]
+        pub fn update_config(&mut self, patch: ConfigPatch) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+
+            let min_lock = patch.min_lock.unwrap_or(self.scheduled_min_lock());
+            let max_lock = patch.max_lock.unwrap_or(self.max_lock);
+            if min_lock > max_lock {
+                return Err(VestingError::InvalidLockPeriod);
+            }
+            for bps in [patch.claim_fee_bps, patch.burn_bps, patch.early_exit_penalty_bps, patch.reward_rate_bps]
+                .into_iter()
+                .flatten()
+            {
+                Self::ensure_valid_bps(bps)?;
+            }
+            // Same limits as set_deposit_fee_bps and set_fee_buffer_bps
+            if patch.deposit_fee_bps.is_some_and(|bps| bps > MAX_DEPOSIT_FEE_BPS)
+                || patch.fee_buffer_bps.is_some_and(|bps| bps >= MAX_BPS)
+            {
+                return Err(VestingError::InvalidBasisPoints);
+            }
+
+            let mut changed = Vec::new();
+            if let Some(min_lock) = patch.min_lock.filter(|min_lock| *min_lock != self.scheduled_min_lock()) {
+                self.apply_min_lock(min_lock);
+                changed.push(ConfigField::MinLock);
+            }
+            Self::patch_field(&mut self.max_lock, patch.max_lock, ConfigField::MaxLock, &mut changed);
+            Self::patch_field(&mut self.claim_fee_bps, patch.claim_fee_bps, ConfigField::ClaimFeeBps, &mut changed);
+            Self::patch_field(&mut self.burn_bps, patch.burn_bps, ConfigField::BurnBps, &mut changed);
+            Self::patch_field(&mut self.deposit_fee_bps, patch.deposit_fee_bps, ConfigField::DepositFeeBps, &mut changed);
+            Self::patch_field(&mut self.fee_buffer_bps, patch.fee_buffer_bps, ConfigField::FeeBufferBps, &mut changed);
+            Self::patch_field(&mut self.xcm_execution_fee, patch.xcm_execution_fee, ConfigField::XcmExecutionFee, &mut changed);
+            Self::patch_field(
+                &mut self.early_exit_penalty_bps,
+                patch.early_exit_penalty_bps,
+                ConfigField::EarlyExitPenaltyBps,
+                &mut changed,
+            );
+            Self::patch_field(&mut self.reward_rate_bps, patch.reward_rate_bps, ConfigField::RewardRateBps, &mut changed);
+            Self::patch_field(&mut self.tvl_cap, patch.tvl_cap, ConfigField::TvlCap, &mut changed);
+            Self::patch_field(&mut self.treasury, patch.treasury, ConfigField::Treasury, &mut changed);
+            Self::patch_field(&mut self.rounding, patch.rounding, ConfigField::Rounding, &mut changed);
+
+            if !changed.is_empty() {
+                self.env().emit_event(ConfigUpdated {
+                    changed,
+                    admin: self.env().caller(),
+                });
+            }
+            Ok(())
+        }
+
+        fn patch_field<T: PartialEq>(slot: &mut T, value: Option<T>, field: ConfigField, changed: &mut Vec<ConfigField>) {
+            if let Some(value) = value {
+                if *slot != value {
+                    *slot = value;
+                    changed.push(field);
+                }
+            }
+        }
+
+        #[ink(message)]
+        pub fn pause_deposits(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.deposits_paused = true;
+            self.env().emit_event(DepositsPaused {
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unpause_deposits(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.deposits_paused = false;
+            self.env().emit_event(DepositsUnpaused {
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        // Supported Assets
+        #[ink(message)]
+        pub fn add_supported_asset(&mut self, asset_id: AssetId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if !self.supported_assets.contains(&asset_id) {
+                if self.supported_assets.len() >= MAX_SUPPORTED_ASSETS {
+                    return Err(VestingError::TooManyAssets);
+                }
+                self.supported_assets.push(asset_id);
+            }
+            Ok(())
+        }
+
+        // Existing deposits of a removed asset stay claimable; only new deposits are blocked
+        #[ink(message)]
+        pub fn remove_supported_asset(&mut self, asset_id: AssetId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.supported_assets.retain(|id| *id != asset_id);
+            Ok(())
+        }
+
+        // Deposit presets; removing one shifts the indices of the presets after it
+        #[ink(message)]
+        pub fn add_deposit_preset(&mut self, asset_id: AssetId, lock_secs: u64) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.ensure_supported_asset(&asset_id)?;
+            self.ensure_valid_lock(lock_secs)?;
+            if self.deposit_presets.len() >= MAX_DEPOSIT_PRESETS {
+                return Err(VestingError::TooManyPresets);
+            }
+            self.deposit_presets.push((asset_id, lock_secs));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn remove_deposit_preset(&mut self, preset_index: u32) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if preset_index as usize >= self.deposit_presets.len() {
+                return Err(VestingError::InvalidPreset);
+            }
+            self.deposit_presets.remove(preset_index as usize);
+            Ok(())
+        }
+
+        // All-or-nothing: any invalid entry rejects the whole batch
+        #[ink(message)]
+        pub fn register_asset_metadata_batch(
+            &mut self,
+            entries: Vec<(AssetId, AssetMetadata)>,
+        ) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if entries.len() > MAX_SUPPORTED_ASSETS {
+                return Err(VestingError::BatchTooLarge);
+            }
+            for (asset_id, metadata) in &entries {
+                self.ensure_supported_asset(asset_id)?;
+                if metadata.symbol.is_empty()
+                    || metadata.symbol.len() > MAX_SYMBOL_LEN
+                    || core::str::from_utf8(&metadata.symbol).is_err()
+                    || metadata.decimals > MAX_DECIMALS
+                {
+                    return Err(VestingError::InvalidAssetMetadata);
+                }
+            }
+
+            for (asset_id, metadata) in entries {
+                self.asset_metadata.insert(&asset_id, &metadata);
+                self.env().emit_event(AssetMetadataRegistered {
+                    asset_id,
+                    symbol: metadata.symbol,
+                    decimals: metadata.decimals,
+                });
+            }
+            Ok(())
+        }
+
+        // Routes deposits of `asset_id` through a PSP22 token contract; None goes back to the precompile
+        #[ink(message)]
+        pub fn set_asset_contract(&mut self, asset_id: AssetId, token: Option<AccountId>) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            match token {
+                Some(token) => {
+                    self.asset_contracts.insert(&asset_id, &token);
+                }
+                None => self.asset_contracts.remove(&asset_id),
+            }
+            Ok(())
+        }
+
+        // Moves what the vault holds of a deprecated asset beyond what it still owes: the
+        // treasury, the reward pool, escrowed large deposits, prefunded fee credits,
+        // exported deposits awaiting release and burned fees.
+        // Only allowed once nothing is locked in it; returns the amount moved.
+        #[ink(message)]
+        pub fn sweep_asset_dust(&mut self, asset_id: AssetId, to: AccountId) -> Result<Balance, VestingError> {
+            self.ensure_admin()?;
+            if self.supported_assets.contains(&asset_id) {
+                return Err(VestingError::AssetStillSupported);
+            }
+            if self.locked_by_asset.get(&asset_id).unwrap_or(0) > 0 {
+                return Err(VestingError::AssetStillLocked);
+            }
+
+            let held = self.call_assets_precompile_balance(self.env().account_id(), asset_id.clone())?;
+            let amount = held.saturating_sub(self.owed_in_asset(&asset_id));
+            if amount > 0 {
+                self.call_assets_precompile_transfer_out(to, amount, asset_id.clone())?;
+            }
+
+            self.env().emit_event(AssetDustSwept {
+                asset_id,
+                to,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        // Funds held in `asset_id` that belong to someone other than the depositors
+        fn owed_in_asset(&self, asset_id: &AssetId) -> Balance {
+            self.treasury_by_asset.get(asset_id).unwrap_or(0)
+                + self.reward_pools.get(asset_id).unwrap_or(0)
+                + self.pending_by_asset.get(asset_id).unwrap_or(0)
+                + self.fee_credits_by_asset.get(asset_id).unwrap_or(0)
+                + self.migrating_by_asset.get(asset_id).unwrap_or(0)
+                + self.burned_by_asset.get(asset_id).unwrap_or(0)
+        }
+
+        // 0 removes the requirement
+        #[ink(message)]
+        pub fn set_asset_min_balance(&mut self, asset_id: AssetId, min_balance: Balance) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if min_balance == 0 {
+                self.asset_min_balances.remove(&asset_id);
+            } else {
+                self.asset_min_balances.insert(&asset_id, &min_balance);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_asset_min_balance(&self, asset_id: AssetId) -> Balance {
+            self.asset_min_balances.get(&asset_id).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn set_reconciliation_tolerance(&mut self, tolerance: Balance) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.reconciliation_tolerance = tolerance;
+            Ok(())
+        }
+
+        // Compares the vault's real asset balance with `locked_by_asset`.
+        // Returns held minus tracked; negative means the vault is short.
+        #[ink(message)]
+        pub fn reconcile(&self, asset_id: AssetId) -> Result<i128, VestingError> {
+            let held = self.call_assets_precompile_balance(self.env().account_id(), asset_id.clone())?;
+            let tracked = self.locked_by_asset.get(&asset_id).unwrap_or(0);
+
+            if held.abs_diff(tracked) > self.reconciliation_tolerance {
+                self.env().emit_event(ReconciliationMismatch {
+                    asset_id,
+                    held,
+                    tracked,
+                });
+            }
+
+            Ok(held as i128 - tracked as i128)
+        }
+
+        // Outflow circuit breaker: if claims release more than `threshold` within
+        // `window_secs`, further claims are paused until the admin resumes them.
+        // This caps how much a compromised key or a drain bug can move out at once.
+        #[ink(message)]
+        pub fn set_outflow_breaker(
+            &mut self,
+            enabled: bool,
+            threshold: Balance,
+            window_secs: u64,
+        ) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.outflow_breaker_enabled = enabled;
+            self.outflow_threshold = threshold;
+            self.outflow_window = window_secs;
+            self.outflow_window_start = self.env().block_timestamp();
+            self.outflow_in_window = 0;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unpause_claims(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.claims_paused = false;
+            self.outflow_window_start = self.env().block_timestamp();
+            self.outflow_in_window = 0;
+            self.env().emit_event(ClaimsUnpaused {
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        // Stops claims (e.g. during a destination outage) while deposits stay open
+        #[ink(message)]
+        pub fn pause_claims(&mut self) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.claims_paused = true;
+            self.env().emit_event(ClaimsPaused {
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        // Claims the backend may pay gas for: net payout at most `cap`, optionally
+        // only for allowlisted accounts
+        #[ink(message)]
+        pub fn set_sponsor_policy(&mut self, cap: Balance, requires_allowlist: bool) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.sponsor_cap = cap;
+            self.sponsor_requires_allowlist = requires_allowlist;
+            Ok(())
+        }
+
+        // Raises what the sponsored-claims program may spend. The gas itself is paid by
+        // the backend's account; the budget only bounds how much of it is sponsored.
+        #[ink(message)]
+        pub fn fund_sponsorship_budget(&mut self, amount: Balance) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.sponsorship_budget = self.sponsorship_budget.saturating_add(amount);
+            Ok(())
+        }
+
+        // Charges the cost of a claim the backend sponsored for `account`. The cost is
+        // already spent, so a claim that overshoots takes what's left of the budget.
+        #[ink(message)]
+        pub fn record_sponsored_claim(&mut self, account: AccountId, cost: Balance) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            let before = self.sponsorship_budget;
+            self.sponsorship_budget = before.saturating_sub(cost);
+            self.env().emit_event(SponsoredClaimRecorded {
+                account,
+                cost,
+                remaining: self.sponsorship_budget,
+            });
+            if before > 0 && self.sponsorship_budget == 0 {
+                self.env().emit_event(SponsorshipExhausted {
+                    timestamp: self.env().block_timestamp(),
+                });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_sponsorship_budget(&self) -> Balance {
+            self.sponsorship_budget
+        }
+
+        // Lowering the cap below total_locked only blocks new deposits
+        #[ink(message)]
+        pub fn set_tvl_cap(&mut self, cap: Option<Balance>) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.tvl_cap = cap;
+            Ok(())
+        }
+
+        // 0, the default, disables early_withdraw altogether
+        #[ink(message)]
+        pub fn set_early_exit_penalty_bps(&mut self, bps: u16) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            Self::ensure_valid_bps(bps)?;
+            self.early_exit_penalty_bps = bps;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_reward_rate_bps(&mut self, bps: u16) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            Self::ensure_valid_bps(bps)?;
+            self.reward_rate_bps = bps;
+            Ok(())
+        }
+
+        // Moves fees collected in `asset_id` into that asset's reward pool. Both are
+        // internal balances of funds the vault already holds, so nothing is transferred.
+        #[ink(message)]
+        pub fn fund_rewards_from_treasury(&mut self, asset_id: AssetId, amount: Balance) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            let treasury = self.treasury_by_asset.get(&asset_id).unwrap_or(0);
+            if amount > treasury {
+                return Err(VestingError::InsufficientTreasury);
+            }
+            self.treasury_by_asset.insert(&asset_id, &(treasury - amount));
+            self.treasury_balance -= amount;
+            let pool = self.reward_pools.get(&asset_id).unwrap_or(0) + amount;
+            self.reward_pools.insert(&asset_id, &pool);
+            self.reward_pool += amount;
+
+            self.env().emit_event(RewardPoolFunded {
+                asset_id,
+                amount,
+                reward_pool: pool,
+                admin: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        // Pays out accrued rewards in the deposit's asset and leaves the principal locked.
+        // If the pool can't cover everything, it pays what's there and only that share
+        // of the accrual period is consumed.
+        #[ink(message)]
+        pub fn claim_rewards(&mut self) -> Result<Balance, VestingError> {
+            let caller = self.env().caller();
+            self.ensure_claims_open()?;
+            let info = self.deposits.get(caller).ok_or(VestingError::NoDepositFound)?;
+            let (since, until, pending) = self.reward_accrual(caller, &info);
+            if pending == 0 {
+                return Err(VestingError::NoRewards);
+            }
+            // Only the pool of the deposit's own asset can pay it
+            let pool = self.reward_pools.get(&info.asset_id).unwrap_or(0);
+            if pool == 0 {
+                return Err(VestingError::RewardPoolEmpty);
+            }
+
+            let paid = pending.min(pool);
+            let consumed = (u128::from(until - since) * paid / pending) as u64;
+            self.reward_pools.insert(&info.asset_id, &(pool - paid));
+            self.reward_pool -= paid;
+            self.reward_checkpoints.insert(caller, &(since + consumed));
+            self.pay_out(caller, paid, info.asset_id.clone())?;
+            // Rewards leave the vault like any claim, so they count toward the breaker
+            self.record_outflow(paid);
+
+            self.env().emit_event(RewardsClaimed {
+                user: caller,
+                amount: paid,
+                asset_id: info.asset_id,
+            });
+
+            Ok(paid)
+        }
+
+        // (from, to, amount): rewards accrue on the principal until unlock
+        fn reward_accrual(&self, owner: AccountId, info: &DepositInfo) -> (Timestamp, Timestamp, Balance) {
+            let now = self.env().block_timestamp();
+            let since = self
+                .reward_checkpoints
+                .get(owner)
+                .or_else(|| self.lock_starts.get(owner))
+                .unwrap_or(now);
+            let until = now.min(info.unlock_timestamp).max(since);
+            let reward = self.div_rounded(
+                info.amount
+                    .saturating_mul(u128::from(self.reward_rate_bps))
+                    .saturating_mul(u128::from(until - since)),
+                u128::from(MAX_BPS) * u128::from(YEAR_MS),
+            );
+            (since, until, reward)
+        }
+
+        #[ink(message)]
+        pub fn pending_reward(&self, account: AccountId) -> Balance {
+            self.deposits
+                .get(account)
+                .map_or(0, |info| self.reward_accrual(account, &info).2)
+        }
+
+        // Across all assets
+        #[ink(message)]
+        pub fn get_reward_pool(&self) -> Balance {
+            self.reward_pool
+        }
+
+        #[ink(message)]
+        pub fn get_reward_pool_for(&self, asset_id: AssetId) -> Balance {
+            self.reward_pools.get(&asset_id).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_treasury_balance_for(&self, asset_id: AssetId) -> Balance {
+            self.treasury_by_asset.get(&asset_id).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn set_max_destinations_per_account(&mut self, limit: u32) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.max_destinations_per_account = limit;
+            Ok(())
+        }
+
+        // Distinct destination parachains the account currently has open positions to
+        fn open_destinations(&self, account: AccountId) -> Vec<u32> {
+            let mut destinations = Vec::new();
+            if let Some(info) = self.deposits.get(account) {
+                destinations.push(info.destination_parachain);
+            }
+            if let Some(schedule) = self.schedules.get(account) {
+                if !destinations.contains(&schedule.destination_parachain) {
+                    destinations.push(schedule.destination_parachain);
+                }
+            }
+            destinations
+        }
+
+        fn ensure_destination_capacity(&self, account: AccountId, destination: u32) -> Result<(), VestingError> {
+            let destinations = self.open_destinations(account);
+            if !destinations.contains(&destination)
+                && destinations.len() as u32 >= self.max_destinations_per_account
+            {
+                return Err(VestingError::TooManyDestinations);
+            }
+            Ok(())
+        }
+
+        // Credit only pays fees charged in the asset it was prefunded in, so a claim of
+        // `claimed` may use it only if the destination's fee asset matches
+        fn usable_fee_credit(&self, user: AccountId, destination: u32, claimed: &AssetId) -> Balance {
+            let fee_asset = self.fee_asset_for(destination, claimed).ok();
+            match self.fee_credits.get((user, destination)) {
+                Some((credit_asset, credit)) if Some(&credit_asset) == fee_asset.as_ref() => credit,
+                _ => 0,
+            }
+        }
+
+        // Takes up to `needed` from the user's prefunded credit for `destination`.
+        // Callers bound `needed` by usable_fee_credit, which checks the credit's asset.
+        fn consume_fee_credit(&mut self, user: AccountId, destination: u32, needed: Balance) -> Balance {
+            if needed == 0 {
+                return 0;
+            }
+            let Some((asset_id, credit)) = self.fee_credits.get((user, destination)) else {
+                return 0;
+            };
+            let used = credit.min(needed);
+            if used == credit {
+                self.fee_credits.remove((user, destination));
+            } else {
+                self.fee_credits.insert((user, destination), &(asset_id.clone(), credit - used));
+            }
+            let credits = self.fee_credits_by_asset.get(&asset_id).unwrap_or(0).saturating_sub(used);
+            self.fee_credits_by_asset.insert(&asset_id, &credits);
+            used
+        }
+
+        // Destination allowlist; while empty every parachain is accepted
+        #[ink(message)]
+        pub fn add_allowed_destination(&mut self, parachain_id: u32) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if !self.allowed_destinations.contains(&parachain_id) {
+                if self.allowed_destinations.len() >= MAX_ALLOWED_DESTINATIONS {
+                    return Err(VestingError::TooManyDestinations);
+                }
+                self.allowed_destinations.push(parachain_id);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn remove_allowed_destination(&mut self, parachain_id: u32) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.allowed_destinations.retain(|id| *id != parachain_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_claim_destination_policy(&mut self, required: bool, approval: bool) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.claim_destinations_required = required;
+            self.claim_destination_approval = approval;
+            Ok(())
+        }
+
+        // Registers where the caller's claims may go. Under the approval policy the
+        // entry is only a request until the admin approves it.
+        #[ink(message)]
+        pub fn register_claim_destination(&mut self, parachain: u32, account: [u8; 32]) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            self.ensure_destination_allowed(parachain)?;
+
+            let entry = (parachain, account);
+            let allowed = self.allowed_claim_destinations.get(caller).unwrap_or_default();
+            if allowed.contains(&entry) {
+                return Ok(());
+            }
+
+            if self.claim_destination_approval {
+                let mut pending = self.pending_claim_destinations.get(caller).unwrap_or_default();
+                if !pending.contains(&entry) {
+                    if pending.len() >= MAX_CLAIM_DESTINATIONS {
+                        return Err(VestingError::TooManyDestinations);
+                    }
+                    pending.push(entry);
+                    self.pending_claim_destinations.insert(caller, &pending);
+                }
+                self.env().emit_event(ClaimDestinationRequested { user: caller, parachain, account });
+                return Ok(());
+            }
+
+            self.add_claim_destination(caller, entry)
+        }
+
+        #[ink(message)]
+        pub fn approve_claim_destination(
+            &mut self,
+            user: AccountId,
+            parachain: u32,
+            account: [u8; 32],
+        ) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            let entry = (parachain, account);
+            let mut pending = self.pending_claim_destinations.get(user).unwrap_or_default();
+            let index = pending
+                .iter()
+                .position(|requested| *requested == entry)
+                .ok_or(VestingError::DestinationNotRegistered)?;
+
+            self.add_claim_destination(user, entry)?;
+            pending.remove(index);
+            if pending.is_empty() {
+                self.pending_claim_destinations.remove(user);
+            } else {
+                self.pending_claim_destinations.insert(user, &pending);
+            }
+            Ok(())
+        }
+
+        // Drops the destination from both the registered and the requested lists
+        #[ink(message)]
+        pub fn deregister_claim_destination(&mut self, parachain: u32, account: [u8; 32]) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            let entry = (parachain, account);
+
+            let mut allowed = self.allowed_claim_destinations.get(caller).unwrap_or_default();
+            let mut pending = self.pending_claim_destinations.get(caller).unwrap_or_default();
+            let before = allowed.len() + pending.len();
+            allowed.retain(|registered| *registered != entry);
+            pending.retain(|requested| *requested != entry);
+            if allowed.len() + pending.len() == before {
+                return Err(VestingError::DestinationNotRegistered);
+            }
+
+            if allowed.is_empty() {
+                self.allowed_claim_destinations.remove(caller);
+            } else {
+                self.allowed_claim_destinations.insert(caller, &allowed);
+            }
+            if pending.is_empty() {
+                self.pending_claim_destinations.remove(caller);
+            } else {
+                self.pending_claim_destinations.insert(caller, &pending);
+            }
+
+            self.env().emit_event(ClaimDestinationDeregistered { user: caller, parachain, account });
+            Ok(())
+        }
+
+        fn add_claim_destination(&mut self, user: AccountId, entry: (u32, [u8; 32])) -> Result<(), VestingError> {
+            let mut allowed = self.allowed_claim_destinations.get(user).unwrap_or_default();
+            if allowed.contains(&entry) {
+                return Ok(());
+            }
+            if allowed.len() >= MAX_CLAIM_DESTINATIONS {
+                return Err(VestingError::TooManyDestinations);
+            }
+            allowed.push(entry);
+            self.allowed_claim_destinations.insert(user, &allowed);
+            self.env().emit_event(ClaimDestinationRegistered {
+                user,
+                parachain: entry.0,
+                account: entry.1,
+            });
+            Ok(())
+        }
+
+        fn ensure_destination_registered(
+            &self,
+            owner: AccountId,
+            parachain: u32,
+            account: [u8; 32],
+        ) -> Result<(), VestingError> {
+            if !self.claim_destinations_required {
+                return Ok(());
+            }
+            let allowed = self.allowed_claim_destinations.get(owner).unwrap_or_default();
+            if !allowed.contains(&(parachain, account)) {
+                return Err(VestingError::DestinationNotRegistered);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_claim_destinations(&self, account: AccountId) -> Vec<(u32, [u8; 32])> {
+            self.allowed_claim_destinations.get(account).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_pending_claim_destinations(&self, account: AccountId) -> Vec<(u32, [u8; 32])> {
+            self.pending_claim_destinations.get(account).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn set_emergency_local_payout(&mut self, enabled: bool) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.emergency_local_payout = enabled;
+            Ok(())
+        }
+
+        // Recovery path for users whose destination chain was deprecated
+        #[ink(message)]
+        pub fn admin_set_destination(
+            &mut self,
+            account: AccountId,
+            new_parachain: u32,
+            new_account: [u8; 32],
+        ) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.ensure_destination_listed(new_parachain)?;
+
+            let mut info = self.deposits.get(account)
+                .ok_or(VestingError::NoDepositFound)?;
+            let old_parachain = info.destination_parachain;
+            let old_account = info.destination_account;
+
+            info.destination_parachain = new_parachain;
+            info.destination_account = new_account;
+            self.deposits.insert(account, &info);
+            self.deposits_root = None;
+
+            self.env().emit_event(DestinationOverridden {
+                account,
+                old_parachain,
+                old_account,
+                new_parachain,
+                new_account,
+                admin: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        // Gasless destination change: `user` signs the payload off-chain and anyone relays it.
+        // The signed message is the SCALE encoding of (SET_DESTINATION_DOMAIN, vault account,
+        // user, new_parachain, new_account, nonce); `nonce` must equal get_destination_nonce
+        // and is consumed on success. Uses the sr25519_verify host function, which chains
+        // only expose through the contracts pallet's unstable interface.
+        #[ink(message)]
+        pub fn set_destination_signed(
+            &mut self,
+            user: AccountId,
+            new_parachain: u32,
+            new_account: [u8; 32],
+            nonce: u64,
+            signature: [u8; 64],
+        ) -> Result<(), VestingError> {
+            if nonce != self.destination_nonces.get(user).unwrap_or(0) {
+                return Err(VestingError::InvalidNonce);
+            }
+            let payload = (
+                SET_DESTINATION_DOMAIN,
+                self.env().account_id(),
+                user,
+                new_parachain,
+                new_account,
+                nonce,
+            )
+                .encode();
+            let public_key: &[u8; 32] = user.as_ref();
+            self.env()
+                .sr25519_verify(&signature, &payload, public_key)
+                .map_err(|_| VestingError::InvalidSignature)?;
+
+            self.ensure_destination_allowed(new_parachain)?;
+            let mut info = self.deposits.get(user).ok_or(VestingError::NoDepositFound)?;
+            let old_parachain = info.destination_parachain;
+            let old_account = info.destination_account;
+
+            info.destination_parachain = new_parachain;
+            info.destination_account = new_account;
+            self.deposits.insert(user, &info);
+            self.deposits_root = None;
+            self.destination_nonces.insert(user, &(nonce + 1));
+
+            self.env().emit_event(DestinationChangedSigned {
+                account: user,
+                old_parachain,
+                old_account,
+                new_parachain,
+                new_account,
+                nonce,
+                relayer: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_destination_nonce(&self, account: AccountId) -> u64 {
+            self.destination_nonces.get(account).unwrap_or(0)
+        }
+
+        // Parachain renumbering: points deposits and schedules at `old_parachain` to
+        // `new_parachain`, along with the owners' fee credits and registered claim
+        // destinations for it. Pages over depositor index positions [offset, offset + limit)
+        // like `deposits_for_destination`; returns how many accounts were rewritten.
+        #[ink(message)]
+        pub fn migrate_destination(
+            &mut self,
+            old_parachain: u32,
+            new_parachain: u32,
+            offset: u32,
+            limit: u32,
+        ) -> Result<u32, VestingError> {
+            self.ensure_admin()?;
+            self.ensure_destination_listed(new_parachain)?;
+
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.depositor_count);
+            let mut accounts = 0;
+            for position in offset..end {
+                let Some(account) = self.depositors.get(position) else {
+                    continue;
+                };
+                let mut migrated = false;
+                if let Some(mut info) = self.deposits.get(account) {
+                    if info.destination_parachain == old_parachain {
+                        info.destination_parachain = new_parachain;
+                        self.deposits.insert(account, &info);
+                        self.deposits_root = None;
+                        migrated = true;
+                    }
+                }
+                if let Some(mut schedule) = self.schedules.get(account) {
+                    if schedule.destination_parachain == old_parachain {
+                        schedule.destination_parachain = new_parachain;
+                        self.schedules.insert(account, &schedule);
+                        migrated = true;
+                    }
+                }
+                if migrated {
+                    self.migrate_account_destination(account, old_parachain, new_parachain);
+                    accounts += 1;
+                }
+            }
+
+            self.env().emit_event(DestinationMigrated {
+                old_parachain,
+                new_parachain,
+                accounts,
+                admin: self.env().caller(),
+            });
+
+            Ok(accounts)
+        }
+
+        fn migrate_account_destination(&mut self, account: AccountId, old_parachain: u32, new_parachain: u32) {
+            // A credit in another asset than the one already held for the new parachain
+            // can't be merged; it stays under the old parachain
+            if let Some((asset_id, credit)) = self.fee_credits.get((account, old_parachain)) {
+                match self.fee_credits.get((account, new_parachain)) {
+                    Some((existing_asset, existing)) if existing_asset == asset_id => {
+                        self.fee_credits.insert((account, new_parachain), &(asset_id, existing + credit));
+                        self.fee_credits.remove((account, old_parachain));
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.fee_credits.insert((account, new_parachain), &(asset_id, credit));
+                        self.fee_credits.remove((account, old_parachain));
+                    }
+                }
+            }
+
+            let mut registered = self.allowed_claim_destinations.get(account).unwrap_or_default();
+            let mut changed = false;
+            for entry in registered.iter_mut() {
+                if entry.0 == old_parachain {
+                    entry.0 = new_parachain;
+                    changed = true;
+                }
+            }
+            if changed {
+                let mut deduped: Vec<(u32, [u8; 32])> = Vec::new();
+                for entry in registered {
+                    if !deduped.contains(&entry) {
+                        deduped.push(entry);
+                    }
+                }
+                self.allowed_claim_destinations.insert(account, &deduped);
+            }
+        }
+
+        // Legal holds and early releases. Not available during an emergency, when every
+        // deposit is claimable anyway, and the new unlock must lie in the future so an
+        // early release is always announced by UnlockAdjusted before funds can move.
+        // An early release also needs guardian_threshold approvals of this exact unlock.
+        #[ink(message)]
+        pub fn admin_set_unlock(&mut self, account: AccountId, new_unlock: Timestamp) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if self.emergency_mode {
+                return Err(VestingError::EmergencyModeActive);
+            }
+            if new_unlock <= self.env().block_timestamp() {
+                return Err(VestingError::InvalidLockPeriod);
+            }
+
+            let mut info = self.deposits.get(account)
+                .ok_or(VestingError::NoDepositFound)?;
+            let old = info.unlock_timestamp;
+            if new_unlock < old {
+                if self.unlock_approval_count(account, new_unlock) < self.guardian_threshold {
+                    return Err(VestingError::NotEnoughApprovals);
+                }
+                self.unlock_approvals.remove(account);
+            }
+            info.unlock_timestamp = new_unlock;
+            self.deposits.insert(account, &info);
+            self.deposits_root = None;
+            self.unlock_holds.insert(account, &());
+            let remaining = new_unlock - self.env().block_timestamp();
+            self.set_lock_weight(account, info.amount.saturating_mul(remaining as u128))?;
+
+            self.env().emit_event(UnlockAdjusted {
+                account,
+                old,
+                new: new_unlock,
+                admin: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        // Staged migration: hands the deposit over to a successor vault. The record is
+        // removed here and the account marked so it can't be claimed or imported back;
+        // the funds are set aside in migrating_by_asset until `release_migrating_funds`
+        // sends them to the new vault.
+        #[ink(message)]
+        pub fn export_and_mark(&mut self, account: AccountId) -> Result<DepositInfo, VestingError> {
+            self.ensure_admin()?;
+            if self.migrated.contains(account) {
+                return Err(VestingError::AlreadyMigrated);
+            }
+
+            let info = self.deposits.take(account)
+                .ok_or(VestingError::NoDepositFound)?;
+            self.deposits_root = None;
+            self.decrease_locked(&info.asset_id, info.amount + info.reserved_fee);
+            self.set_lock_weight(account, 0)?;
+            self.lock_starts.remove(account);
+            self.unlock_holds.remove(account);
+            self.unlock_approvals.remove(account);
+            self.reward_checkpoints.remove(account);
+            self.unindex_if_closed(account);
+            self.migrated.insert(account, &());
+            let migrating = self.migrating_by_asset.get(&info.asset_id).unwrap_or(0)
+                + info.amount
+                + info.reserved_fee;
+            self.migrating_by_asset.insert(&info.asset_id, &migrating);
+
+            self.env().emit_event(DepositMigrated {
+                account,
+                asset_id: info.asset_id.clone(),
+                amount: info.amount,
+                reserved_fee: info.reserved_fee,
+                unlock_timestamp: info.unlock_timestamp,
+                admin: self.env().caller(),
+            });
+
+            Ok(info)
+        }
+
+        // Sends everything exported in `asset_id` so far to the successor vault; returns
+        // the amount sent
+        #[ink(message)]
+        pub fn release_migrating_funds(&mut self, asset_id: AssetId, to: AccountId) -> Result<Balance, VestingError> {
+            self.ensure_admin()?;
+            let amount = self.migrating_by_asset.take(&asset_id).unwrap_or(0);
+            if amount > 0 {
+                self.call_assets_precompile_transfer_out(to, amount, asset_id.clone())?;
+            }
+
+            self.env().emit_event(MigratingFundsReleased {
+                asset_id,
+                to,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        #[ink(message)]
+        pub fn get_migrating(&self, asset_id: AssetId) -> Balance {
+            self.migrating_by_asset.get(&asset_id).unwrap_or(0)
+        }
+
+        // Receiving side of `export_and_mark`. The funds must already be held by this vault.
+        #[ink(message)]
+        pub fn import_deposit(&mut self, account: AccountId, info: DepositInfo) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if self.migrated.contains(account) {
+                return Err(VestingError::AlreadyMigrated);
+            }
+            if self.deposits.contains(account) {
+                return Err(VestingError::DepositAlreadyExists);
+            }
+            self.ensure_supported_asset(&info.asset_id)?;
+
+            let remaining = info.unlock_timestamp.saturating_sub(self.env().block_timestamp());
+            self.deposits.insert(account, &info);
+            self.deposits_root = None;
+            self.index_depositor(account);
+            self.increase_locked(&info.asset_id, info.amount + info.reserved_fee);
+            self.set_lock_weight(account, info.amount.saturating_mul(remaining as u128))?;
+            // The original start isn't exported, so the penalty pro-rates from import
+            self.lock_starts.insert(account, &self.env().block_timestamp());
+
+            self.env().emit_event(DepositImported {
+                account,
+                asset_id: info.asset_id.clone(),
+                amount: info.amount,
+                reserved_fee: info.reserved_fee,
+                unlock_timestamp: info.unlock_timestamp,
+                admin: self.env().caller(),
+            });
+
+            self.ensure_backed(&info.asset_id)
+        }
+
+        #[ink(message)]
+        pub fn set_xcm_status(&mut self, xcm_hash: [u8; 32], status: XcmStatus) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if !self.xcm_statuses.contains(xcm_hash) {
+                return Err(VestingError::UnknownXcmHash);
+            }
+            self.xcm_statuses.insert(xcm_hash, &status);
+            self.env().emit_event(XcmStatusUpdated { xcm_hash, status });
+            Ok(())
+        }
+
+        // Guardian changes must always leave a reachable threshold: at least one
+        // approval and no more than there are guardians
+        #[ink(message)]
+        pub fn add_guardian(&mut self, guardian: AccountId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if self.guardians.contains(&guardian) {
+                return Err(VestingError::AlreadyGuardian);
+            }
+            if self.guardians.len() >= MAX_GUARDIANS {
+                return Err(VestingError::BatchTooLarge);
+            }
+            self.guardians.push(guardian);
+            self.env().emit_event(GuardianAdded {
+                guardian,
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn remove_guardian(&mut self, guardian: AccountId) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            let position = self.guardians.iter().position(|g| *g == guardian)
+                .ok_or(VestingError::NotGuardian)?;
+            if self.guardian_threshold as usize > self.guardians.len() - 1 {
+                return Err(VestingError::InvalidThreshold);
+            }
+            self.guardians.swap_remove(position);
+            self.emergency_approvals.retain(|g| *g != guardian);
+            self.env().emit_event(GuardianRemoved {
+                guardian,
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_threshold(&mut self, threshold: u32) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            if threshold == 0 || threshold as usize > self.guardians.len() {
+                return Err(VestingError::InvalidThreshold);
+            }
+            let old = self.guardian_threshold;
+            self.guardian_threshold = threshold;
+            self.env().emit_event(ThresholdChanged {
+                old,
+                new: threshold,
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        // Counts towards guardian_threshold for the next emergency_unlock; approving
+        // twice is a no-op
+        #[ink(message)]
+        pub fn approve_emergency(&mut self) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            if !self.guardians.contains(&caller) {
+                return Err(VestingError::NotGuardian);
+            }
+            if !self.emergency_approvals.contains(&caller) {
+                self.emergency_approvals.push(caller);
+            }
+            self.env().emit_event(EmergencyApproved {
+                guardian: caller,
+                approvals: self.emergency_approvals.len() as u32,
+                threshold: self.guardian_threshold,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_emergency_approvals(&self) -> Vec<AccountId> {
+            self.emergency_approvals.clone()
+        }
+
+        // Counts towards guardian_threshold for moving `account`'s unlock earlier to
+        // exactly `new_unlock`. Approving another time starts a new round.
+        #[ink(message)]
+        pub fn approve_early_unlock(&mut self, account: AccountId, new_unlock: Timestamp) -> Result<(), VestingError> {
+            let caller = self.env().caller();
+            if !self.guardians.contains(&caller) {
+                return Err(VestingError::NotGuardian);
+            }
+            let mut approvers = match self.unlock_approvals.get(account) {
+                Some((proposed, approvers)) if proposed == new_unlock => approvers,
+                _ => Vec::new(),
+            };
+            if !approvers.contains(&caller) {
+                approvers.push(caller);
+            }
+            self.unlock_approvals.insert(account, &(new_unlock, approvers));
+
+            self.env().emit_event(EarlyUnlockApproved {
+                account,
+                new_unlock,
+                guardian: caller,
+                approvals: self.unlock_approval_count(account, new_unlock),
+                threshold: self.guardian_threshold,
+            });
+            Ok(())
+        }
+
+        // Approvals of removed guardians no longer count
+        fn unlock_approval_count(&self, account: AccountId, new_unlock: Timestamp) -> u32 {
+            match self.unlock_approvals.get(account) {
+                Some((proposed, approvers)) if proposed == new_unlock => {
+                    approvers.iter().filter(|g| self.guardians.contains(g)).count() as u32
+                }
+                _ => 0,
+            }
+        }
+
+        fn ensure_destination_allowed(&self, parachain_id: u32) -> Result<(), VestingError> {
+            if !self.allowed_destinations.is_empty() && !self.allowed_destinations.contains(&parachain_id) {
+                return Err(VestingError::DestinationNotAllowed);
+            }
+            Ok(())
+        }
+
+        // Stricter than ensure_destination_allowed: moving funds somewhere other than
+        // where the owner deposited needs an explicit allowlist entry, even while the
+        // list is empty and deposits may go anywhere
+        fn ensure_destination_listed(&self, parachain_id: u32) -> Result<(), VestingError> {
+            if !self.allowed_destinations.contains(&parachain_id) {
+                return Err(VestingError::DestinationNotAllowed);
+            }
+            Ok(())
+        }
+
+        fn ensure_claims_open(&self) -> Result<(), VestingError> {
+            if self.claims_paused {
+                return Err(VestingError::ClaimsPaused);
+            }
+            Ok(())
+        }
+
+        // The claim that crosses the threshold still goes through; the ones after it are blocked
+        fn record_outflow(&mut self, amount: Balance) {
+            if !self.outflow_breaker_enabled {
+                return;
+            }
+
+            let now = self.env().block_timestamp();
+            if now.saturating_sub(self.outflow_window_start) >= self.outflow_window {
+                self.outflow_window_start = now;
+                self.outflow_in_window = 0;
+            }
+            self.outflow_in_window += amount;
+
+            if self.outflow_in_window > self.outflow_threshold && !self.claims_paused {
+                self.claims_paused = true;
+                self.env().emit_event(AutoEmergencyTriggered {
+                    outflow: self.outflow_in_window,
+                    threshold: self.outflow_threshold,
+                    window_start: self.outflow_window_start,
+                    timestamp: now,
+                });
+            }
+        }
+
+        fn ensure_deposits_open(&self, caller: AccountId) -> Result<(), VestingError> {
+            if self.deposits_paused {
+                return Err(VestingError::DepositsPaused);
+            }
+            if self.allowlist_enabled && !self.allowlist.contains(caller) {
+                return Err(VestingError::NotAllowlisted);
+            }
+            Ok(())
+        }
+
+        fn ensure_supported_asset(&self, asset_id: &AssetId) -> Result<(), VestingError> {
+            if !self.supported_assets.contains(asset_id) {
+                return Err(VestingError::AssetNotSupported);
+            }
+            Ok(())
+        }
+
+        fn ensure_valid_lock(&self, lock_secs: u64) -> Result<(), VestingError> {
+            if lock_secs < self.effective_min_lock() || lock_secs > self.max_lock {
+                return Err(VestingError::InvalidLockPeriod);
+            }
+            Ok(())
+        }
+
+        // The depositor index holds every account with an open deposit or schedule
+        fn index_depositor(&mut self, account: AccountId) {
+            if self.depositor_positions.contains(account) {
+                return;
+            }
+            self.depositors.insert(self.depositor_count, &account);
+            self.depositor_positions.insert(account, &self.depositor_count);
+            self.depositor_count += 1;
+            self.deposits_root = None;
+        }
+
+        // Swap-remove so the index stays dense
+        fn unindex_if_closed(&mut self, account: AccountId) {
+            if self.deposits.contains(account) || self.schedules.contains(account) {
+                return;
+            }
+            let Some(position) = self.depositor_positions.take(account) else {
+                return;
+            };
+            let last = self.depositor_count - 1;
+            if position != last {
+                if let Some(moved) = self.depositors.get(last) {
+                    self.depositors.insert(position, &moved);
+                    self.depositor_positions.insert(moved, &position);
+                }
+            }
+            self.depositors.remove(last);
+            self.deposits_root = None;
+            self.depositor_count = last;
+        }
+
+        // Scans index positions [offset, offset + limit) and returns the matching deposits.
+        // Callers page by advancing `offset` by `limit`, even if fewer matches came back.
+        fn page_deposits<F>(&self, offset: u32, limit: u32, filter: F) -> Vec<(AccountId, Balance, Timestamp)>
+        where
+            F: Fn(&DepositInfo) -> bool,
+        {
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.depositor_count);
+            (offset..end)
+                .filter_map(|position| self.depositors.get(position))
+                .filter_map(|account| self.deposits.get(account).map(|info| (account, info)))
+                .filter(|(_, info)| filter(info))
+                .map(|(account, info)| (account, info.amount, info.unlock_timestamp))
+                .collect()
+        }
+
+        fn set_lock_weight(&mut self, owner: AccountId, weight: u128) -> Result<(), VestingError> {
+            let old = self.lock_weights.get(owner).unwrap_or(0);
+            self.total_lock_weight = self
+                .total_lock_weight
+                .saturating_sub(old)
+                .checked_add(weight)
+                .ok_or(VestingError::ArithmeticOverflow)?;
+            if weight == 0 {
+                self.lock_weights.remove(owner);
+            } else {
+                self.lock_weights.insert(owner, &weight);
+            }
+            Ok(())
+        }
+
+        // Accounting guard: the vault must hold at least what it tracks as locked for
+        // an asset. Runs after every deposit and claim; an Err reverts the whole call.
+        // Only assets backed by a PSP22 contract are really checked: the simulated
+        // precompile reports locked_by_asset as the vault's balance, so precompile
+        // assets always pass until the real balance query is wired in.
+        fn ensure_backed(&self, asset_id: &AssetId) -> Result<(), VestingError> {
+            let held = self.call_assets_precompile_balance(self.env().account_id(), asset_id.clone())?;
+            if self.locked_by_asset.get(asset_id).unwrap_or(0) > held {
+                return Err(VestingError::LockedExceedsBalance);
+            }
+            Ok(())
+        }
+
+        fn increase_locked(&mut self, asset_id: &AssetId, amount: Balance) {
+            let before = self.total_locked;
+            self.total_locked += amount;
+            if let Some(cap) = self.tvl_cap {
+                // Dividing first would round small caps down to a warning level of 0;
+                // only a cap too large to multiply falls back to it
+                let warning = cap
+                    .checked_mul(Balance::from(TVL_CAP_WARNING_BPS))
+                    .map_or(cap / Balance::from(MAX_BPS) * Balance::from(TVL_CAP_WARNING_BPS), |scaled| {
+                        scaled / Balance::from(MAX_BPS)
+                    });
+                if before < warning && self.total_locked >= warning {
+                    self.env().emit_event(TvlCapNearlyReached {
+                        total_locked: self.total_locked,
+                        cap,
+                    });
+                }
+            }
+            let locked = self.locked_by_asset.get(asset_id).unwrap_or(0);
+            self.locked_by_asset.insert(asset_id, &(locked + amount));
+            self.record_tvl_checkpoint();
+        }
+
+        fn decrease_locked(&mut self, asset_id: &AssetId, amount: Balance) {
+            self.total_locked -= amount;
+            let locked = self.locked_by_asset.get(asset_id).unwrap_or(0);
+            self.locked_by_asset.insert(asset_id, &(locked - amount));
+            self.record_tvl_checkpoint();
+        }
+
+        // Several changes within one block collapse into a single checkpoint
+        fn record_tvl_checkpoint(&mut self) {
+            let block_number = self.env().block_number();
+            let latest = self
+                .tvl_checkpoint_count
+                .checked_sub(1)
+                .map(|index| index % MAX_TVL_CHECKPOINTS);
+            let same_block = latest
+                .and_then(|slot| self.tvl_checkpoints.get(slot))
+                .map_or(false, |(block, _)| block == block_number);
+
+            let slot = match latest {
+                Some(slot) if same_block => slot,
+                _ => {
+                    let slot = self.tvl_checkpoint_count % MAX_TVL_CHECKPOINTS;
+                    self.tvl_checkpoint_count += 1;
+                    slot
+                }
+            };
+            self.tvl_checkpoints.insert(slot, &(block_number, self.total_locked));
+
+            if self.verbose_events {
+                self.env().emit_event(TvlCheckpoint {
+                    block_number,
+                    total_locked: self.total_locked,
+                });
+            }
+        }
+
+        // What claiming `info` would pay out right now. The claim path applies exactly
+        // these numbers, so this must stay free of side effects.
+        fn quote_claim(&self, owner: AccountId, info: &DepositInfo) -> ClaimPreview {
+            let (fee_to_treasury, fee_burned) = self.claim_fee_split(info.amount);
+            let fee = fee_to_treasury + fee_burned;
+
+            // The reserved buffer pays BuyExecution first, then any prefunded credit;
+            // whatever is left of the buffer goes to the user. A local emergency payout
+            // executes nothing remotely and returns the whole buffer.
+            let execution_fee = if self.pays_out_locally() { 0 } else { self.xcm_execution_fee };
+            let from_buffer = info.reserved_fee.min(execution_fee);
+            let credit = self.usable_fee_credit(owner, info.destination_parachain, &info.asset_id);
+            let fee_credit_used = credit.min(execution_fee - from_buffer);
+
+            ClaimPreview {
+                gross: info.amount,
+                fee,
+                execution_fee: from_buffer + fee_credit_used,
+                fee_credit_used,
+                // Rewards are harvested separately with claim_rewards; any not
+                // harvested by the time the principal is claimed are forfeited
+                reward: 0,
+                net: info.amount - fee + (info.reserved_fee - from_buffer),
+                destination_parachain: info.destination_parachain,
+                destination_account: info.destination_account,
+            }
+        }
+
+        // penalty_bps = base × remaining / total lock, clamped to [0, base]. Nothing is
+        // charged once the deposit has unlocked or during an emergency.
+        fn early_exit_penalty(&self, owner: AccountId, info: &DepositInfo) -> (u16, Balance) {
+            let now = self.env().block_timestamp();
+            if self.emergency_mode || now >= info.unlock_timestamp {
+                return (0, 0);
+            }
+            let start = self.lock_starts.get(owner).unwrap_or(now);
+            let total = info.unlock_timestamp.saturating_sub(start);
+            if total == 0 {
+                return (0, 0);
+            }
+            let remaining = (info.unlock_timestamp - now).min(total);
+            let bps = self.div_rounded(
+                u128::from(self.early_exit_penalty_bps) * u128::from(remaining),
+                u128::from(total),
+            ) as u16;
+            (bps, self.bps_of(info.amount, bps))
+        }
+
+        // Split the claim fee into the treasury's share and the burned share
+        fn claim_fee_split(&self, amount: Balance) -> (Balance, Balance) {
+            let fee = self.bps_of(amount, self.claim_fee_bps);
+            let fee_burned = self.bps_of(fee, self.burn_bps);
+            (fee - fee_burned, fee_burned)
+        }
+
+        fn collect_claim_fee(&mut self, asset_id: &AssetId, fee_to_treasury: Balance, fee_burned: Balance) {
+            self.credit_treasury(asset_id, fee_to_treasury);
+            // Burned fees stay locked in the vault for good; see burned_by_asset
+            if fee_burned == 0 {
+                return;
+            }
+            self.total_burned += fee_burned;
+            let burned = self.burned_by_asset.get(asset_id).unwrap_or(0);
+            self.burned_by_asset.insert(asset_id, &(burned + fee_burned));
+        }
+
+        fn credit_treasury(&mut self, asset_id: &AssetId, amount: Balance) {
+            if amount == 0 {
+                return;
+            }
+            self.treasury_balance += amount;
+            let held = self.treasury_by_asset.get(asset_id).unwrap_or(0);
+            self.treasury_by_asset.insert(asset_id, &(held + amount));
+        }
+
+        fn ensure_admin(&self) -> Result<(), VestingError> {
+            if self.env().caller() != self.admin {
+                return Err(VestingError::UnauthorizedAccess);
+            }
+            Ok(())
+        }
+
+        fn ensure_valid_bps(bps: u16) -> Result<(), VestingError> {
+            if bps > MAX_BPS {
+                return Err(VestingError::InvalidBasisPoints);
+            }
+            Ok(())
+        }
+
+        fn bps_of(&self, amount: Balance, bps: u16) -> Balance {
+            self.div_rounded(amount * Balance::from(bps), Balance::from(MAX_BPS))
+        }
+
+        fn div_rounded(&self, numerator: u128, denominator: u128) -> u128 {
+            let quotient = numerator / denominator;
+            let remainder = numerator % denominator;
+            let round_up = match self.rounding {
+                RoundingMode::Down => false,
+                RoundingMode::Up => remainder > 0,
+                RoundingMode::Nearest => remainder >= denominator - remainder,
+            };
+            if round_up { quotient + 1 } else { quotient }
+        }
+
+        // Assets Precompile Integration (simulated)
+        // Pulls funds into the vault. Every deposit path calls this before touching
+        // storage, so a failed transfer leaves no state behind.
+        fn call_assets_precompile_transfer(
+            &self,
+            from: AccountId,
+            amount: Balance,
+            asset_id: AssetId,
+        ) -> Result<(), VestingError> {
+            if let Some(token) = self.asset_contracts.get(&asset_id) {
+                return self.call_token_transfer_from(token, from, amount);
+            }
+
+            // In a real implementation, this would call the Assets precompile
+            // using something like:
+            // 
+            // let call = build_call::<DefaultEnvironment>()
+            //     .call_type(Call::new(ASSETS_PRECOMPILE_ADDRESS))
+            //     .exec_input(ExecutionInput::new(Selector::new([0x84, 0xa1, 0x5d, 0xa1])))
+            //     .returns::<()>()
+            //     .params();
+            //
+            // self.env().invoke_contract(&call).unwrap();
+            
+            // For demonstration, we'll just simulate the transfer
+            ink::env::debug_println!("Assets precompile transfer: {} tokens of asset {:?} from {:?}", amount, asset_id, from);
+            
+            Ok(())
+        }
+
+        fn call_token_transfer_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            amount: Balance,
+        ) -> Result<(), VestingError> {
+            // Any PSP22 error decodes as its variant index; only a clean Ok counts
+            let result = build_call::<DefaultEnvironment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(PSP22_TRANSFER_FROM))
+                        .push_arg(from)
+                        .push_arg(self.env().account_id())
+                        .push_arg(amount)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), u8>>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err(VestingError::TransferFailed),
+            }
+        }
+
+        fn call_assets_precompile_balance(
+            &self,
+            owner: AccountId,
+            asset_id: AssetId,
+        ) -> Result<Balance, VestingError> {
+            // In a real implementation, this would query the Assets precompile
+            // for `owner`'s balance of `asset_id`.
+            
+            if let Some(token) = self.asset_contracts.get(&asset_id) {
+                return build_call::<DefaultEnvironment>()
+                    .call(token)
+                    .exec_input(ExecutionInput::new(Selector::new(PSP22_BALANCE_OF)).push_arg(owner))
+                    .returns::<Balance>()
+                    .try_invoke()
+                    .ok()
+                    .and_then(|result| result.ok())
+                    .ok_or(VestingError::BalanceQueryFailed);
+            }
+
+            // For demonstration, the vault holds exactly what it tracks
+            ink::env::debug_println!("Assets precompile balance query: asset {:?} of {:?}", asset_id, owner);
+            
+            if owner == self.env().account_id() {
+                return Ok(self.locked_by_asset.get(&asset_id).unwrap_or(0));
+            }
+            Ok(0)
+        }
+
+        // Local payout that must leave the vault's account of `asset_id` alive. The
+        // asset's min_balance isn't readable through the precompile, so the admin
+        // mirrors it with set_asset_min_balance. Sweeping dust deliberately empties
+        // the account and goes straight to the transfer instead.
+        fn pay_out(&self, to: AccountId, amount: Balance, asset_id: AssetId) -> Result<(), VestingError> {
+            let min_balance = self.asset_min_balances.get(&asset_id).unwrap_or(0);
+            if min_balance > 0 {
+                let held = self.call_assets_precompile_balance(self.env().account_id(), asset_id.clone())?;
+                if held.saturating_sub(amount) < min_balance {
+                    return Err(VestingError::WouldReapAccount);
+                }
+            }
+            self.call_assets_precompile_transfer_out(to, amount, asset_id)
         }
 
-        // Assets Precompile Integration (simulated)
-        fn call_assets_precompile_transfer(
+        fn call_assets_precompile_transfer_out(
             &self,
-            from: AccountId,
+            to: AccountId,
             amount: Balance,
             asset_id: AssetId,
         ) -> Result<(), VestingError> {
             // In a real implementation, this would call the Assets precompile
-            // using something like:
-            // 
-            // let call = build_call::<DefaultEnvironment>()
-            //     .call_type(Call::new(ASSETS_PRECOMPILE_ADDRESS))
-            //     .exec_input(ExecutionInput::new(Selector::new([0x84, 0xa1, 0x5d, 0xa1])))
-            //     .returns::<()>()
-            //     .params();
-            //
-            // self.env().invoke_contract(&call).unwrap();
+            // to transfer tokens from the contract to `to`.
             
             // For demonstration, we'll just simulate the transfer
-            ink::env::debug_println!("Assets precompile transfer: {} tokens of asset {:?} from {:?}", amount, asset_id, from);
+            ink::env::debug_println!("Assets precompile transfer: {} tokens of asset {:?} to {:?}", amount, asset_id, to);
             
             Ok(())
         }
 
         // XCM Execution (using ink! v5.1.0+ XCM functions)
         fn execute_xcm_transfer(
-            &self,
+            &mut self,
             beneficiary: AccountId,
             amount: Balance,
+            execution_fee: Balance,
             destination_parachain: u32,
             asset_id: AssetId,
         ) -> Result<[u8; 32], VestingError> {
@@ -220,17 +3764,28 @@ mod vesting_vault {
             // This is a simplified example
             
             // Create XCM message for cross-chain transfer
-            let xcm_message = self.build_xcm_message(beneficiary, amount, destination_parachain, asset_id);
+            let fee_asset = self.fee_asset_for(destination_parachain, &asset_id)?;
+            let xcm_message = self.build_xcm_message(
+                beneficiary,
+                amount,
+                execution_fee,
+                fee_asset,
+                destination_parachain,
+                asset_id,
+            );
             
             // Execute XCM (simulated)
             let xcm_hash = self.calculate_xcm_hash(&xcm_message);
+            self.xcm_statuses.insert(xcm_hash, &XcmStatus::Pending);
             
-            self.env().emit_event(XCMExecuted {
-                user: beneficiary,
-                amount,
-                destination: destination_parachain,
-                success: true,
-            });
+            if self.verbose_events {
+                self.env().emit_event(XCMExecuted {
+                    user: beneficiary,
+                    amount,
+                    destination: destination_parachain,
+                    success: true,
+                });
+            }
             
             Ok(xcm_hash)
         }
@@ -239,6 +3794,8 @@ mod vesting_vault {
             &self,
             beneficiary: AccountId,
             amount: Balance,
+            execution_fee: Balance,
+            fee_asset: AssetId,
             destination_parachain: u32,
             asset_id: AssetId,
         ) -> Vec<u8> {
@@ -246,11 +3803,62 @@ mod vesting_vault {
             let mut message = Vec::new();
             message.extend_from_slice(&beneficiary.encode());
             message.extend_from_slice(&amount.to_le_bytes());
+            // BuyExecution fee
+            message.extend_from_slice(&execution_fee.to_le_bytes());
+            message.extend_from_slice(&fee_asset.0.to_le_bytes());
             message.extend_from_slice(&destination_parachain.to_le_bytes());
             message.extend_from_slice(&asset_id.0.to_le_bytes());
             message
         }
 
+        fn fee_asset_for(&self, destination_parachain: u32, claimed: &AssetId) -> Result<AssetId, VestingError> {
+            match self.fee_asset_by_destination.get(destination_parachain) {
+                Some(fee_asset) => Ok(fee_asset),
+                None if self.strict_fee_assets => Err(VestingError::FeeAssetNotConfigured),
+                None => Ok(claimed.clone()),
+            }
+        }
+
+        // None clears the override so the destination pays in the claimed asset again
+        #[ink(message)]
+        pub fn set_fee_asset(&mut self, destination_parachain: u32, fee_asset: Option<AssetId>) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            match fee_asset {
+                Some(fee_asset) => {
+                    self.ensure_supported_asset(&fee_asset)?;
+                    self.fee_asset_by_destination.insert(destination_parachain, &fee_asset);
+                }
+                None => self.fee_asset_by_destination.remove(destination_parachain),
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_strict_fee_assets(&mut self, strict: bool) -> Result<(), VestingError> {
+            self.ensure_admin()?;
+            self.strict_fee_assets = strict;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_fee_asset(&self, destination_parachain: u32) -> Option<AssetId> {
+            self.fee_asset_by_destination.get(destination_parachain)
+        }
+
+        // Account that represents `parachain_id` on this chain, where a sibling's funds
+        // sit in transit: b"sibl" ++ para_id (SCALE, little-endian) zero-padded to 32 bytes
+        #[ink(message)]
+        pub fn destination_sovereign_account(&self, parachain_id: u32) -> [u8; 32] {
+            Self::sibling_sovereign_account(parachain_id)
+        }
+
+        fn sibling_sovereign_account(parachain_id: u32) -> [u8; 32] {
+            let mut account = [0u8; 32];
+            account[..4].copy_from_slice(b"sibl");
+            account[4..8].copy_from_slice(&parachain_id.to_le_bytes());
+            account
+        }
+
         fn calculate_xcm_hash(&self, message: &[u8]) -> [u8; 32] {
             // Simple hash calculation for demo
             let mut hash = [0u8; 32];
@@ -265,19 +3873,637 @@ mod vesting_vault {
             self.deposits.get(account)
         }
 
+        // The caller's own deposit
+        #[ink(message)]
+        pub fn my_deposit(&self) -> Option<DepositInfo> {
+            self.deposits.get(self.env().caller())
+        }
+
+        #[ink(message)]
+        pub fn get_max_destinations_per_account(&self) -> u32 {
+            self.max_destinations_per_account
+        }
+
+        // (asset, amount) of the user's prefunded credit for `destination`
+        #[ink(message)]
+        pub fn get_fee_credit(&self, user: AccountId, destination: u32) -> Option<(AssetId, Balance)> {
+            self.fee_credits.get((user, destination))
+        }
+
+        #[ink(message)]
+        pub fn get_allowed_destinations(&self) -> Vec<u32> {
+            self.allowed_destinations.clone()
+        }
+
+        #[ink(message)]
+        pub fn get_pending_deposit(&self, account: AccountId) -> Option<PendingDeposit> {
+            self.pending_deposits.get(account)
+        }
+
+        // Goes through claimable_deposit like effective_status, so Claimable always
+        // means a claim would pass every check
+        #[ink(message)]
+        pub fn deposit_state(&self, account: AccountId) -> DepositState {
+            let Some(stored) = self.deposits.get(account) else {
+                if let Some(pending) = self.pending_deposits.get(account) {
+                    return DepositState::AwaitingConfirmation { amount: pending.amount };
+                }
+                return match self.last_claim_hashes.get(account) {
+                    Some(xcm_hash) if self.xcm_statuses.get(xcm_hash) == Some(XcmStatus::Pending) => {
+                        DepositState::ClaimPending { xcm_hash }
+                    }
+                    _ => DepositState::None,
+                };
+            };
+
+            match self.claimable_deposit(account, false, None) {
+                Ok(info) => DepositState::Claimable { amount: self.quote_claim(account, &info).net },
+                Err(VestingError::TokensStillLocked) => DepositState::Locked { unlock: stored.unlock_timestamp },
+                Err(VestingError::ClaimsPaused) => DepositState::Frozen,
+                Err(reason) => DepositState::Blocked { reason },
+            }
+        }
+
+        #[ink(message)]
+        pub fn effective_status(&self, account: AccountId) -> EffectiveStatus {
+            match self.claimable_deposit(account, false, None) {
+                Ok(info) => EffectiveStatus::Claimable {
+                    amount: self.quote_claim(account, &info).net,
+                    emergency: self.emergency_mode && self.env().block_timestamp() < info.unlock_timestamp,
+                },
+                Err(VestingError::AlreadyMigrated) => EffectiveStatus::Migrated,
+                Err(VestingError::ClaimsPaused) => EffectiveStatus::ClaimsPaused,
+                Err(VestingError::TokensStillLocked) => EffectiveStatus::Locked {
+                    unlock: self.deposits.get(account).map_or(0, |info| info.unlock_timestamp),
+                },
+                Err(VestingError::DestinationNotRegistered) => EffectiveStatus::DestinationNotRegistered,
+                Err(VestingError::NoDepositFound) => EffectiveStatus::NoDeposit,
+                Err(reason) => EffectiveStatus::Blocked { reason },
+            }
+        }
+
+        // Whether a claim by `account` would succeed right now and falls within the
+        // sponsor policy, so the backend can submit it on the user's behalf
+        #[ink(message)]
+        pub fn sponsorable(&self, account: AccountId) -> bool {
+            if self.sponsorship_budget == 0 {
+                return false;
+            }
+            if self.sponsor_requires_allowlist && !self.allowlist.contains(account) {
+                return false;
+            }
+            match self.claimable_deposit(account, false, None) {
+                Ok(info) => {
+                    let amount = self.quote_claim(account, &info).net;
+                    amount > 0 && amount <= self.sponsor_cap
+                }
+                Err(_) => false,
+            }
+        }
+
+        // (penalty_bps, penalty) that early_withdraw would charge right now
+        #[ink(message)]
+        pub fn preview_early_exit_penalty(&self, owner: AccountId) -> Result<(u16, Balance), VestingError> {
+            let info = self.deposits.get(owner).ok_or(VestingError::NoDepositFound)?;
+            Ok(self.early_exit_penalty(owner, &info))
+        }
+
+        // Breakdown of what `owner` would receive if the deposit were claimed now
+        #[ink(message)]
+        pub fn preview_claim(&self, owner: AccountId) -> Result<ClaimPreview, VestingError> {
+            let info = self.deposits.get(owner).ok_or(VestingError::NoDepositFound)?;
+            Ok(self.quote_claim(owner, &info))
+        }
+
+        #[ink(message)]
+        pub fn get_claim_receipt(&self, receipt_id: u64) -> Option<ClaimReceipt> {
+            self.claim_receipts.get(receipt_id)
+        }
+
+        #[ink(message)]
+        pub fn get_claim_approval(&self, owner: AccountId) -> Option<AccountId> {
+            self.claim_approvals.get(owner)
+        }
+
+        #[ink(message)]
+        pub fn get_schedule(&self, account: AccountId) -> Option<VestingSchedule> {
+            self.schedules.get(account)
+        }
+
+        // Amount that could be claimed right now across the deposit and schedule tranches,
+        // net of fees
+        #[ink(message)]
+        pub fn claimable_now(&self, account: AccountId) -> Balance {
+            self.total_claimable(account)
+        }
+
+        // Net of fees, as the claims would pay out now; a position whose claim would
+        // fail counts as 0. Uses the same checks and quote as deposit_state.
+        #[ink(message)]
+        pub fn total_claimable(&self, account: AccountId) -> Balance {
+            let deposit = self
+                .claimable_deposit(account, false, None)
+                .map_or(0, |info| self.quote_claim(account, &info).net);
+            let tranches = self
+                .claimable_schedule(account)
+                .map_or(0, |(schedule, matured)| self.quote_schedule_claim(account, &schedule, &matured).net);
+            deposit + tranches
+        }
+
+        // Everything the account has in the vault, matured or not: the deposit with its
+        // reserved fee and all schedule tranches
+        #[ink(message)]
+        pub fn total_locked_for(&self, account: AccountId) -> Balance {
+            let deposit = self
+                .deposits
+                .get(account)
+                .map_or(0, |info| info.amount + info.reserved_fee);
+            let tranches: Balance = self
+                .schedules
+                .get(account)
+                .map_or(0, |schedule| schedule.tranches.iter().map(|t| t.amount + t.reserved_fee).sum());
+            deposit + tranches
+        }
+
+        // Distinct assets the account has locked, deposit first. An account holds at most
+        // one deposit and one schedule, so this never exceeds two entries.
+        #[ink(message)]
+        pub fn user_assets(&self, account: AccountId) -> Vec<AssetId> {
+            let mut assets = Vec::new();
+            let deposit = self.deposits.get(account).map(|info| info.asset_id);
+            let schedule = self.schedules.get(account).map(|schedule| schedule.asset_id);
+            for asset_id in deposit.into_iter().chain(schedule) {
+                if !assets.contains(&asset_id) {
+                    assets.push(asset_id);
+                }
+            }
+            assets
+        }
+
+        #[ink(message)]
+        pub fn user_asset_count(&self, account: AccountId) -> u32 {
+            self.user_assets(account).len() as u32
+        }
+
+        #[ink(message)]
+        pub fn get_depositor_count(&self) -> u32 {
+            self.depositor_count
+        }
+
+        // Work queue for a relayer serving one parachain
+        #[ink(message)]
+        pub fn deposits_for_destination(
+            &self,
+            parachain_id: u32,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, Balance, Timestamp)> {
+            self.page_deposits(offset, limit, |info| info.destination_parachain == parachain_id)
+        }
+
+        // Per-position detail behind `locked_by_asset`, paged the same way
+        #[ink(message)]
+        pub fn deposits_by_asset(
+            &self,
+            asset_id: AssetId,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, Balance, Timestamp)> {
+            self.page_deposits(offset, limit, |info| info.asset_id == asset_id)
+        }
+
+        // Deposits unlocking within [from, to], paged over the depositor index like
+        // `deposits_for_destination`. An inverted range matches nothing.
+        #[ink(message)]
+        pub fn deposits_unlocking_between(
+            &self,
+            from: Timestamp,
+            to: Timestamp,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, Balance, Timestamp)> {
+            if from > to {
+                return Vec::new();
+            }
+            self.page_deposits(offset, limit, |info| {
+                (from..=to).contains(&info.unlock_timestamp)
+            })
+        }
+
+        // Soonest upcoming unlock across deposits and schedule tranches. Fails with
+        // TooManyDepositors past MAX_INDEX_SCAN depositors rather than miss an unlock.
+        #[ink(message)]
+        pub fn next_unlock(&self) -> Result<Option<Timestamp>, VestingError> {
+            if self.depositor_count > MAX_INDEX_SCAN {
+                return Err(VestingError::TooManyDepositors);
+            }
+
+            let now = self.env().block_timestamp();
+            let mut next: Option<Timestamp> = None;
+            let mut consider = |unlock: Timestamp| {
+                if unlock > now && next.map_or(true, |n| unlock < n) {
+                    next = Some(unlock);
+                }
+            };
+
+            for position in 0..self.depositor_count {
+                let Some(account) = self.depositors.get(position) else {
+                    continue;
+                };
+                if let Some(info) = self.deposits.get(account) {
+                    consider(info.unlock_timestamp);
+                }
+                if let Some(schedule) = self.schedules.get(account) {
+                    schedule.tranches.iter().for_each(|t| consider(t.unlock_timestamp));
+                }
+            }
+
+            Ok(next)
+        }
+
+        #[ink(message)]
+        pub fn get_tvl_cap(&self) -> Option<Balance> {
+            self.tvl_cap
+        }
+
+        // Maturity ladder: locked amount (principal plus reserved fee) of live deposits
+        // and schedule tranches, grouped by unlock time into `bucket_secs`-wide buckets,
+        // earliest first. Each bucket is keyed by its start. Fails with TooManyDepositors
+        // past MAX_INDEX_SCAN depositors.
+        #[ink(message)]
+        pub fn locked_by_bucket(&self, bucket_secs: u64) -> Result<Vec<(Timestamp, Balance)>, VestingError> {
+            if bucket_secs == 0 {
+                return Err(VestingError::InvalidBucketSize);
+            }
+            if self.depositor_count > MAX_INDEX_SCAN {
+                return Err(VestingError::TooManyDepositors);
+            }
+
+            let mut buckets: Vec<(Timestamp, Balance)> = Vec::new();
+            for position in 0..self.depositor_count {
+                let Some(account) = self.depositors.get(position) else {
+                    continue;
+                };
+                if let Some(info) = self.deposits.get(account) {
+                    Self::add_to_bucket(&mut buckets, bucket_secs, info.unlock_timestamp, info.amount + info.reserved_fee)?;
+                }
+                if let Some(schedule) = self.schedules.get(account) {
+                    for tranche in schedule.tranches {
+                        let locked = tranche.amount + tranche.reserved_fee;
+                        Self::add_to_bucket(&mut buckets, bucket_secs, tranche.unlock_timestamp, locked)?;
+                    }
+                }
+            }
+            Ok(buckets)
+        }
+
+        fn add_to_bucket(
+            buckets: &mut Vec<(Timestamp, Balance)>,
+            bucket_secs: u64,
+            unlock: Timestamp,
+            locked: Balance,
+        ) -> Result<(), VestingError> {
+            let start = unlock - unlock % bucket_secs;
+            match buckets.binary_search_by_key(&start, |(bucket, _)| *bucket) {
+                Ok(index) => buckets[index].1 += locked,
+                Err(index) => {
+                    if buckets.len() >= MAX_UNLOCK_BUCKETS {
+                        return Err(VestingError::TooManyBuckets);
+                    }
+                    buckets.insert(index, (start, locked));
+                }
+            }
+            Ok(())
+        }
+
+        // Merkle root over blake2_256(account ++ DepositInfo) leaves, so light clients can
+        // check a deposit against one hash. Served from the cache that refresh_deposits_root
+        // fills, else computed on the spot. Fails with TooManyDepositors past MAX_INDEX_SCAN
+        // depositors.
+        #[ink(message)]
+        pub fn deposits_merkle_root(&self) -> Result<[u8; 32], VestingError> {
+            match self.deposits_root {
+                Some(root) => Ok(root),
+                None => self.compute_deposits_root(),
+            }
+        }
+
+        // Caches the current root so deposits_merkle_root stays cheap until the next
+        // deposit change. Anyone may call it; the root depends only on stored deposits.
+        #[ink(message)]
+        pub fn refresh_deposits_root(&mut self) -> Result<[u8; 32], VestingError> {
+            let root = self.compute_deposits_root()?;
+            self.deposits_root = Some(root);
+            Ok(root)
+        }
+
+        fn compute_deposits_root(&self) -> Result<[u8; 32], VestingError> {
+            let mut level = self.deposit_leaves()?;
+            while level.len() > 1 {
+                level = Self::merkle_level(&level);
+            }
+            Ok(level.first().copied().unwrap_or([0u8; 32]))
+        }
+
+        #[ink(message)]
+        pub fn deposit_proof(&self, account: AccountId) -> Result<MerkleProof, VestingError> {
+            let info = self.deposits.get(account).ok_or(VestingError::NoDepositFound)?;
+            let leaf = Self::deposit_leaf(account, &info);
+            let mut level = self.deposit_leaves()?;
+            let leaf_index = level
+                .iter()
+                .position(|hash| *hash == leaf)
+                .ok_or(VestingError::NoDepositFound)?;
+            let leaf_count = level.len() as u32;
+
+            let mut siblings = Vec::new();
+            let mut index = leaf_index;
+            while level.len() > 1 {
+                let sibling = index ^ 1;
+                if sibling < level.len() {
+                    siblings.push(level[sibling]);
+                }
+                level = Self::merkle_level(&level);
+                index /= 2;
+            }
+
+            Ok(MerkleProof {
+                leaf,
+                leaf_index: leaf_index as u32,
+                leaf_count,
+                siblings,
+            })
+        }
+
+        fn deposit_leaves(&self) -> Result<Vec<[u8; 32]>, VestingError> {
+            if self.depositor_count > MAX_INDEX_SCAN {
+                return Err(VestingError::TooManyDepositors);
+            }
+            Ok((0..self.depositor_count)
+                .filter_map(|position| self.depositors.get(position))
+                .filter_map(|account| self.deposits.get(account).map(|info| Self::deposit_leaf(account, &info)))
+                .collect())
+        }
+
+        fn deposit_leaf(account: AccountId, info: &DepositInfo) -> [u8; 32] {
+            let mut leaf = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(account, info), &mut leaf);
+            leaf
+        }
+
+        fn merkle_level(nodes: &[[u8; 32]]) -> Vec<[u8; 32]> {
+            nodes
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let mut parent = [0u8; 32];
+                        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(left, right), &mut parent);
+                        parent
+                    }
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect()
+        }
+
         #[ink(message)]
         pub fn get_total_locked(&self) -> Balance {
             self.total_locked
         }
 
+        // Retained checkpoints, oldest first; only the last MAX_TVL_CHECKPOINTS are kept
+        #[ink(message)]
+        pub fn total_locked_history(&self, offset: u32, limit: u32) -> Vec<(BlockNumber, Balance)> {
+            let retained = self.tvl_checkpoint_count.min(MAX_TVL_CHECKPOINTS);
+            let oldest = self.tvl_checkpoint_count - retained;
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(retained);
+            (offset..end)
+                .filter_map(|index| self.tvl_checkpoints.get((oldest + index) % MAX_TVL_CHECKPOINTS))
+                .collect()
+        }
+
         #[ink(message)]
         pub fn is_emergency_mode(&self) -> bool {
             self.emergency_mode
         }
 
+        // The trigger details of the last emergency are kept after operations resume
+        #[ink(message)]
+        pub fn get_emergency_info(&self) -> EmergencyInfo {
+            EmergencyInfo {
+                active: self.emergency_mode,
+                triggered_at: self.emergency_triggered_at,
+                triggered_by: self.emergency_triggered_by,
+                expiry: None,
+            }
+        }
+
+        // Claims made since emergency mode was triggered; reset by resume_operations
+        #[ink(message)]
+        pub fn emergency_claim_count(&self) -> u32 {
+            self.emergency_claim_count
+        }
+
+        #[ink(message)]
+        pub fn get_last_emergency_toggle(&self) -> Option<Timestamp> {
+            self.last_emergency_toggle
+        }
+
         #[ink(message)]
         pub fn get_supported_assets(&self) -> Vec<AssetId> {
             self.supported_assets.clone()
         }
+
+        // One flag per requested asset, in order; unknown ids are false
+        #[ink(message)]
+        pub fn is_supported_batch(&self, asset_ids: Vec<AssetId>) -> Result<Vec<bool>, VestingError> {
+            if asset_ids.len() > MAX_PAGE_SIZE as usize {
+                return Err(VestingError::BatchTooLarge);
+            }
+            Ok(asset_ids
+                .iter()
+                .map(|asset_id| self.supported_assets.contains(asset_id))
+                .collect())
+        }
+
+        // Whether a deposit of `asset_id` to `destination_parachain` would be accepted and
+        // could later be claimed there. Mirrors the deposit checks, plus the fee asset
+        // that strict_fee_assets requires at claim time.
+        #[ink(message)]
+        pub fn can_route(&self, asset_id: AssetId, destination_parachain: u32) -> bool {
+            self.ensure_supported_asset(&asset_id).is_ok()
+                && self.ensure_destination_allowed(destination_parachain).is_ok()
+                && self.fee_asset_for(destination_parachain, &asset_id).is_ok()
+        }
+
+        // Supported assets currently holding funds. Deposits left in a removed asset
+        // are not listed here; `get_locked_by_asset` still reports them.
+        #[ink(message)]
+        pub fn active_assets(&self) -> Vec<(AssetId, Balance)> {
+            self.supported_assets
+                .iter()
+                .map(|asset_id| (asset_id.clone(), self.locked_by_asset.get(asset_id).unwrap_or(0)))
+                .filter(|(_, locked)| *locked > 0)
+                .collect()
+        }
+
+        // Governance weighting: principal × lock length as of the last time the lock was set
+        #[ink(message)]
+        pub fn get_lock_weight(&self, account: AccountId) -> u128 {
+            self.lock_weights.get(account).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn total_lock_weight(&self) -> u128 {
+            self.total_lock_weight
+        }
+
+        #[ink(message)]
+        pub fn get_presets(&self) -> Vec<(AssetId, u64)> {
+            self.deposit_presets.clone()
+        }
+
+        #[ink(message)]
+        pub fn get_asset_config(&self, asset_id: AssetId) -> Option<AssetConfig> {
+            if !self.supported_assets.contains(&asset_id) {
+                return None;
+            }
+            Some(AssetConfig {
+                metadata: self.asset_metadata.get(&asset_id),
+                token_contract: self.asset_contracts.get(&asset_id),
+                locked: self.locked_by_asset.get(&asset_id).unwrap_or(0),
+                min_lock: self.effective_min_lock(),
+                max_lock: self.max_lock,
+                tvl_cap: self.tvl_cap,
+                deposits_paused: self.deposits_paused,
+                asset_id,
+            })
+        }
+
+        // Pages over supported assets in registration order
+        #[ink(message)]
+        pub fn get_all_asset_configs(&self, offset: u32, limit: u32) -> Vec<AssetConfig> {
+            self.supported_assets
+                .iter()
+                .skip(offset as usize)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .filter_map(|asset_id| self.get_asset_config(asset_id.clone()))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_asset_metadata(&self, asset_id: AssetId) -> Option<AssetMetadata> {
+            self.asset_metadata.get(&asset_id)
+        }
+
+        #[ink(message)]
+        pub fn get_locked_by_asset(&self, asset_id: AssetId) -> Balance {
+            self.locked_by_asset.get(&asset_id).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_config(&self) -> VaultConfig {
+            VaultConfig {
+                admin: self.admin,
+                treasury: self.treasury,
+                min_lock: self.effective_min_lock(),
+                max_lock: self.max_lock,
+                claim_fee_bps: self.claim_fee_bps,
+                burn_bps: self.burn_bps,
+                fee_buffer_bps: self.fee_buffer_bps,
+                xcm_execution_fee: self.xcm_execution_fee,
+                deposits_paused: self.deposits_paused,
+                emergency_mode: self.emergency_mode,
+                allowlist_enabled: self.allowlist_enabled,
+                reconciliation_tolerance: self.reconciliation_tolerance,
+                claims_paused: self.claims_paused,
+                outflow_breaker_enabled: self.outflow_breaker_enabled,
+                outflow_threshold: self.outflow_threshold,
+                outflow_window: self.outflow_window,
+                max_destinations_per_account: self.max_destinations_per_account,
+                emergency_toggle_cooldown: self.emergency_toggle_cooldown,
+                large_deposit_threshold: self.large_deposit_threshold,
+                deposit_fee_bps: self.deposit_fee_bps,
+                verbose_events: self.verbose_events,
+                sponsor_cap: self.sponsor_cap,
+                sponsor_requires_allowlist: self.sponsor_requires_allowlist,
+                sponsorship_budget: self.sponsorship_budget,
+                tvl_cap: self.tvl_cap,
+                early_exit_penalty_bps: self.early_exit_penalty_bps,
+                reward_pool: self.reward_pool,
+                reward_rate_bps: self.reward_rate_bps,
+                claim_destinations_required: self.claim_destinations_required,
+                claim_destination_approval: self.claim_destination_approval,
+                emergency_local_payout: self.emergency_local_payout,
+                strict_fee_assets: self.strict_fee_assets,
+                rounding: self.rounding,
+            }
+        }
+
+        // The account holding the vault's assets, for balance checks and XCM origins
+        #[ink(message)]
+        pub fn vault_account(&self) -> AccountId {
+            self.env().account_id()
+        }
+
+        #[ink(message)]
+        pub fn get_xcm_status(&self, xcm_hash: [u8; 32]) -> Option<XcmStatus> {
+            self.xcm_statuses.get(xcm_hash)
+        }
+
+        // (guardians, threshold)
+        #[ink(message)]
+        pub fn get_guardians(&self) -> (Vec<AccountId>, u32) {
+            (self.guardians.clone(), self.guardian_threshold)
+        }
+
+        // Everyone who controls the vault, read straight from storage so it can't drift
+        #[ink(message)]
+        pub fn get_governance(&self) -> GovernanceInfo {
+            GovernanceInfo {
+                admin: self.admin,
+                pending_admin: None,
+                guardians: self.guardians.clone(),
+                threshold: self.guardian_threshold,
+            }
+        }
+
+        #[ink(message)]
+        pub fn is_migrated(&self, account: AccountId) -> bool {
+            self.migrated.contains(account)
+        }
+
+        #[ink(message)]
+        pub fn is_allowlisted(&self, account: AccountId) -> bool {
+            self.allowlist.contains(account)
+        }
+
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> Balance {
+            self.treasury_balance
+        }
+
+        #[ink(message)]
+        pub fn get_total_burned(&self) -> Balance {
+            self.total_burned
+        }
+
+        #[ink(message)]
+        pub fn get_burned(&self, asset_id: AssetId) -> Balance {
+            self.burned_by_asset.get(&asset_id).unwrap_or(0)
+        }
+
+        // Lifetime net paid out by claims
+        #[ink(message)]
+        pub fn get_total_claimed(&self) -> Balance {
+            self.total_claimed
+        }
+
+        #[ink(message)]
+        pub fn get_total_claimed_by_asset(&self, asset_id: AssetId) -> Balance {
+            self.claimed_by_asset.get(&asset_id).unwrap_or(0)
+        }
     }
 }
@@ -1,9 +1,67 @@
 use drink::prelude::*;
-use vesting_vault::{VestingVault, VestingError, AssetId};
+use scale::Decode;
+use drink::{mock_message, session::mocking_api::MockingApi, ContractMock};
+use vesting_vault::{
+    VestingVault, VestingError, AssetId, DepositInfo, EffectiveStatus, RoundingMode, ConfigPatch, VaultConfig,
+};
 
 #[drink::contract_bundle_provider]
 enum BundleProvider {}
 
+// claim_cross_chain to the destination stored with the deposit
+const NO_OVERRIDE: Option<(u32, [u8; 32])> = None;
+
+fn get_total_locked(
+    session: &mut Session,
+    contract_address: AccountId32,
+) -> Result<u128, Box<dyn std::error::Error>> {
+    session.call_and(contract_address, "get_total_locked", &[], NO_ENDOWMENT)?;
+    Ok(session.last_call_return::<u128>().ok_or("no return value")??)
+}
+
+fn get_deposit_info(
+    session: &mut Session,
+    contract_address: AccountId32,
+    account: &AccountId32,
+) -> Result<Option<DepositInfo>, Box<dyn std::error::Error>> {
+    session.call_and(contract_address, "get_deposit_info", &[account.encode()], NO_ENDOWMENT)?;
+    Ok(session.last_call_return::<Option<DepositInfo>>().ok_or("no return value")??)
+}
+
+// total_locked must always equal the sum of live deposits (principal plus reserved fee)
+fn assert_total_locked_invariant(
+    session: &mut Session,
+    contract_address: AccountId32,
+    accounts: &[AccountId32],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut sum = 0u128;
+    for account in accounts {
+        if let Some(info) = get_deposit_info(session, contract_address.clone(), account)? {
+            sum += info.amount + info.reserved_fee;
+        }
+    }
+
+    let total_locked = get_total_locked(session, contract_address)?;
+    assert_eq!(total_locked, sum, "total_locked diverged from the sum of deposits");
+    Ok(())
+}
+
+// The VestingError a call reverted with. ink! wraps the message's `Result<_, VestingError>`
+// in `Result<_, LangError>`; LangError encodes as a single byte.
+fn reverted_with<T: std::fmt::Debug>(
+    result: Result<T, drink::session::error::SessionError>,
+) -> Result<VestingError, Box<dyn std::error::Error>> {
+    match result {
+        Err(drink::session::error::SessionError::CallReverted(data)) => {
+            match <Result<Result<(), VestingError>, u8>>::decode(&mut &data[..])? {
+                Ok(Err(err)) => Ok(err),
+                other => Err(format!("unexpected revert payload: {:?}", other).into()),
+            }
+        }
+        other => Err(format!("call did not revert: {:?}", other).into()),
+    }
+}
+
 #[drink::test]
 fn test_full_vesting_cycle(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
     let admin = AccountId32::new([1u8; 32]);
@@ -70,7 +128,7 @@ fn test_full_vesting_cycle(mut session: Session) -> Result<(), Box<dyn std::erro
     let claim_result = session.call_with_address(
         contract_address,
         "claim_cross_chain",
-        &[],
+        &[NO_OVERRIDE.encode()],
         NO_ENDOWMENT,
         alice,
     )?;
@@ -122,7 +180,7 @@ fn test_time_locked_claim(mut session: Session) -> Result<(), Box<dyn std::error
     let claim_result = session.call_with_address(
         contract_address,
         "claim_cross_chain",
-        &[],
+        &[NO_OVERRIDE.encode()],
         NO_ENDOWMENT,
         alice,
     );
@@ -136,7 +194,7 @@ fn test_time_locked_claim(mut session: Session) -> Result<(), Box<dyn std::error
     let claim_result = session.call_with_address(
         contract_address,
         "claim_cross_chain",
-        &[],
+        &[NO_OVERRIDE.encode()],
         NO_ENDOWMENT,
         alice,
     )?;
@@ -188,3 +246,1928 @@ fn test_asset_support(mut session: Session) -> Result<(), Box<dyn std::error::Er
 
     Ok(())
 }
+
+#[drink::test]
+fn test_allowlist_mode(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Enable the allowlist as admin
+    session.call_with_address(
+        contract_address,
+        "set_allowlist_enabled",
+        &[true.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    let deposit_args = [
+        AssetId(1).encode(),
+        (1000u128).encode(),
+        (120_000u64).encode(),
+        (2000u32).encode(),
+    ];
+
+    // Deposit from a non-listed account (should fail)
+    let deposit_result = session.call_with_address(
+        contract_address,
+        "deposit_with_asset",
+        &deposit_args,
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+
+    assert_eq!(reverted_with(deposit_result)?, VestingError::NotAllowlisted);
+
+    // Allowlist alice and deposit again (should succeed)
+    session.call_with_address(
+        contract_address,
+        "add_to_allowlist",
+        &[alice.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    session.call_with_address(
+        contract_address,
+        "deposit_with_asset",
+        &deposit_args,
+        NO_ENDOWMENT,
+        alice,
+    )?;
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_total_locked_invariant(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+    let charlie = AccountId32::new([4u8; 32]);
+    let accounts = [alice.clone(), bob.clone(), charlie.clone()];
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Multiple deposits with different assets, amounts and locks
+    let deposits = [
+        (alice.clone(), 1, 1000u128, 120_000u64),
+        (bob.clone(), 2, 2500u128, 60_000u64),
+        (charlie.clone(), 1, 400u128, 300_000u64),
+    ];
+    for (account, asset, amount, lock) in deposits {
+        session.call_with_address(
+            contract_address.clone(),
+            "deposit_with_asset",
+            &[
+                AssetId(asset).encode(),
+                amount.encode(),
+                lock.encode(),
+                (2000u32).encode(),
+            ],
+            NO_ENDOWMENT,
+            account,
+        )?;
+        assert_total_locked_invariant(&mut session, contract_address.clone(), &accounts)?;
+    }
+
+    // A second deposit must not overwrite the first one
+    let deposit_result = session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (300u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+
+    assert!(deposit_result.is_err(), "Deposit over an existing deposit should fail");
+    assert_total_locked_invariant(&mut session, contract_address.clone(), &accounts)?;
+
+    // Bob claims once his lock expires
+    session.advance_time(61_000);
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        bob,
+    )?;
+    assert_total_locked_invariant(&mut session, contract_address.clone(), &accounts)?;
+
+    // Charlie claims early through emergency mode
+    session.call_with_address(
+        contract_address.clone(),
+        "emergency_unlock",
+        &[],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        charlie,
+    )?;
+    assert_total_locked_invariant(&mut session, contract_address.clone(), &accounts)?;
+
+    // Alice drains the vault
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        alice,
+    )?;
+    assert_total_locked_invariant(&mut session, contract_address.clone(), &accounts)?;
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 0);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_failed_transfer_records_nothing(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Stub PSP22 token whose transfer_from always fails with InsufficientAllowance
+    let rejecting_token = ContractMock::new().with_message(
+        [0x54, 0xb3, 0xc7, 0x6e],
+        mock_message(|_: (AccountId32, AccountId32, u128, Vec<u8>)| Err::<(), u8>(2)),
+    );
+    let token_address = session.mocking_api().deploy(rejecting_token);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_asset_contract",
+        &[AssetId(1).encode(), Some(token_address).encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    let deposit_result = session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+
+    assert_eq!(reverted_with(deposit_result)?, VestingError::TransferFailed);
+
+    // Nothing was recorded for the rejected deposit
+    assert!(get_deposit_info(&mut session, contract_address.clone(), &alice)?.is_none());
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 0);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_locked_exceeding_balance_is_rejected(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Stub token that reports success on transfer_from but never credits the vault,
+    // so the deposit would leave total_locked above what the vault holds
+    let lying_token = ContractMock::new()
+        .with_message(
+            [0x54, 0xb3, 0xc7, 0x6e],
+            mock_message(|_: (AccountId32, AccountId32, u128, Vec<u8>)| Ok::<(), u8>(())),
+        )
+        .with_message(
+            [0x65, 0x68, 0x38, 0x2f],
+            mock_message(|_: AccountId32| 0u128),
+        );
+    let token_address = session.mocking_api().deploy(lying_token);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_asset_contract",
+        &[AssetId(1).encode(), Some(token_address).encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    let deposit_result = session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+
+    assert_eq!(reverted_with(deposit_result)?, VestingError::LockedExceedsBalance);
+
+    // The guard reverted the whole deposit
+    assert!(get_deposit_info(&mut session, contract_address.clone(), &alice)?.is_none());
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 0);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_multi_account_deposits_are_independent(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+    let charlie = AccountId32::new([4u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Each account deposits a different asset and amount
+    let deposits = [
+        (alice.clone(), 1, 1000u128),
+        (bob.clone(), 2, 2500u128),
+        (charlie.clone(), 1, 400u128),
+    ];
+    for (account, asset, amount) in deposits.clone() {
+        session.call_with_address(
+            contract_address.clone(),
+            "deposit_with_asset",
+            &[
+                AssetId(asset).encode(),
+                amount.encode(),
+                (120_000u64).encode(),
+                (2000u32).encode(),
+            ],
+            NO_ENDOWMENT,
+            account,
+        )?;
+    }
+
+    // Every deposit keeps its own asset and amount
+    let mut infos = Vec::new();
+    for (account, asset, amount) in deposits {
+        let info = get_deposit_info(&mut session, contract_address.clone(), &account)?
+            .ok_or("missing deposit")?;
+        assert_eq!(info.asset_id, AssetId(asset));
+        assert_eq!(info.amount + info.reserved_fee, amount);
+        infos.push(info);
+    }
+
+    let total_locked = get_total_locked(&mut session, contract_address.clone())?;
+    assert_eq!(total_locked, 1000 + 2500 + 400);
+
+    // Bob claims early through emergency mode
+    session.call_with_address(
+        contract_address.clone(),
+        "emergency_unlock",
+        &[],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        bob.clone(),
+    )?;
+
+    // Only Bob's position is gone
+    assert!(get_deposit_info(&mut session, contract_address.clone(), &bob)?.is_none());
+    for (account, before) in [(alice, &infos[0]), (charlie, &infos[2])] {
+        let after = get_deposit_info(&mut session, contract_address.clone(), &account)?
+            .ok_or("missing deposit")?;
+        assert_eq!(after.amount, before.amount);
+        assert_eq!(after.reserved_fee, before.reserved_fee);
+        assert_eq!(after.unlock_timestamp, before.unlock_timestamp);
+    }
+    assert_eq!(get_total_locked(&mut session, contract_address)?, total_locked - 2500);
+
+    Ok(())
+}
+
+// Deploys a vault with a 10% early exit penalty and a 10_000 deposit from alice
+// locked for 100_000
+fn setup_early_exit(
+    session: &mut Session,
+    admin: &AccountId32,
+    alice: &AccountId32,
+) -> Result<AccountId32, Box<dyn std::error::Error>> {
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_early_exit_penalty_bps",
+        &[(1_000u16).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (10_000u128).encode(),
+            (100_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+
+    Ok(contract_address)
+}
+
+// Penalty quoted for alice, then the treasury balance her early exit produced
+fn early_exit_outcome(
+    session: &mut Session,
+    contract_address: AccountId32,
+    alice: &AccountId32,
+) -> Result<((u16, u128), u128), Box<dyn std::error::Error>> {
+    session.call_and(contract_address.clone(), "preview_early_exit_penalty", &[alice.encode()], NO_ENDOWMENT)?;
+    let quoted = session
+        .last_call_return::<Result<(u16, u128), VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "early_withdraw",
+        &[],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    assert!(get_deposit_info(session, contract_address.clone(), alice)?.is_none());
+
+    session.call_and(contract_address, "get_treasury_balance", &[], NO_ENDOWMENT)?;
+    let treasury = session.last_call_return::<u128>().ok_or("no return value")??;
+    Ok((quoted, treasury))
+}
+
+#[drink::test]
+fn test_early_exit_disabled_by_default(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (10_000u128).encode(),
+            (100_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+
+    let result = session.call_with_address(contract_address, "early_withdraw", &[], NO_ENDOWMENT, alice);
+    assert_eq!(reverted_with(result)?, VestingError::EarlyExitDisabled);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_early_exit_refused_under_admin_hold(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let contract_address = setup_early_exit(&mut session, &admin, &alice)?;
+
+    // Legal hold: the admin pushes the unlock further out
+    let info = get_deposit_info(&mut session, contract_address.clone(), &alice)?.ok_or("missing deposit")?;
+    session.call_with_address(
+        contract_address.clone(),
+        "admin_set_unlock",
+        &[alice.encode(), (info.unlock_timestamp + 100_000).encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    let result = session.call_with_address(contract_address, "early_withdraw", &[], NO_ENDOWMENT, alice);
+    assert_eq!(reverted_with(result)?, VestingError::UnlockHeld);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_shortening_unlock_needs_guardian_approval(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let guardian = AccountId32::new([5u8; 32]);
+    let contract_address = setup_early_exit(&mut session, &admin, &alice)?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "add_guardian",
+        &[guardian.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "set_threshold",
+        &[(1u32).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    let info = get_deposit_info(&mut session, contract_address.clone(), &alice)?.ok_or("missing deposit")?;
+    let earlier = info.unlock_timestamp - 50_000;
+    let unapproved = session.call_with_address(
+        contract_address.clone(),
+        "admin_set_unlock",
+        &[alice.encode(), earlier.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    );
+    assert_eq!(reverted_with(unapproved)?, VestingError::NotEnoughApprovals);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "approve_early_unlock",
+        &[alice.encode(), earlier.encode()],
+        NO_ENDOWMENT,
+        guardian,
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "admin_set_unlock",
+        &[alice.encode(), earlier.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    let info = get_deposit_info(&mut session, contract_address, &alice)?.ok_or("missing deposit")?;
+    assert_eq!(info.unlock_timestamp, earlier);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_early_exit_at_deposit_pays_full_penalty(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let contract_address = setup_early_exit(&mut session, &admin, &alice)?;
+
+    // 0% of the lock elapsed
+    let (quoted, treasury) = early_exit_outcome(&mut session, contract_address, &alice)?;
+    assert_eq!(quoted, (1_000, 1_000));
+    assert_eq!(treasury, 1_000);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_early_exit_halfway_pays_half_penalty(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let contract_address = setup_early_exit(&mut session, &admin, &alice)?;
+
+    // 50% of the lock elapsed
+    session.advance_time(50_000);
+    let (quoted, treasury) = early_exit_outcome(&mut session, contract_address, &alice)?;
+    assert_eq!(quoted, (500, 500));
+    assert_eq!(treasury, 500);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_exit_at_unlock_pays_no_penalty(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let contract_address = setup_early_exit(&mut session, &admin, &alice)?;
+
+    // 100% of the lock elapsed
+    session.advance_time(100_000);
+    let (quoted, treasury) = early_exit_outcome(&mut session, contract_address, &alice)?;
+    assert_eq!(quoted, (0, 0));
+    assert_eq!(treasury, 0);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_grant_is_claimable_only_by_recipient(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let employer = AccountId32::new([2u8; 32]);
+    let employee = AccountId32::new([3u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    let grant_args = [
+        employee.encode(),
+        AssetId(1).encode(),
+        (1000u128).encode(),
+        (120_000u64).encode(),
+        (2000u32).encode(),
+    ];
+
+    // Nobody can lock up an account's single deposit slot uninvited
+    let uninvited = session.call_with_address(
+        contract_address.clone(),
+        "grant",
+        &grant_args,
+        NO_ENDOWMENT,
+        employer.clone(),
+    );
+    assert_eq!(reverted_with(uninvited)?, VestingError::GrantNotApproved);
+
+    // Once approved, the employer funds a deposit owned by the employee
+    session.call_with_address(
+        contract_address.clone(),
+        "approve_grantor",
+        &[employer.encode()],
+        NO_ENDOWMENT,
+        employee.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "grant",
+        &grant_args,
+        NO_ENDOWMENT,
+        employer.clone(),
+    )?;
+
+    assert!(get_deposit_info(&mut session, contract_address.clone(), &employer)?.is_none());
+    let info = get_deposit_info(&mut session, contract_address.clone(), &employee)?
+        .ok_or("missing deposit")?;
+    assert_eq!(info.amount + info.reserved_fee, 1000);
+
+    session.advance_time(121_000);
+
+    // The funder has nothing to claim; the recipient does
+    let employer_claim = session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        employer,
+    );
+    assert!(employer_claim.is_err());
+
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        employee.clone(),
+    )?;
+    assert!(get_deposit_info(&mut session, contract_address.clone(), &employee)?.is_none());
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 0);
+
+    Ok(())
+}
+
+// xorshift64*: enough randomness for operation sequences, and stable across platforms
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+// Random deposits, claims and time jumps, checking the accounting invariants after each
+// step. Set VAULT_FUZZ_SEED to replay a failing run.
+#[drink::test]
+fn test_randomized_operations_keep_invariants(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let seed = std::env::var("VAULT_FUZZ_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(0x5eed_1234_u64);
+    println!("VAULT_FUZZ_SEED={}", seed);
+    let mut rng = SeededRng(seed.max(1));
+
+    let admin = AccountId32::new([1u8; 32]);
+    let accounts: Vec<AccountId32> = (2u8..8).map(|byte| AccountId32::new([byte; 32])).collect();
+    let assets = [1u32, 2];
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Time since deploy, and each open deposit's unlock on that clock. Steps are
+    // multiples of 10_000 and locks end on 5_000, so no claim lands exactly on an unlock.
+    let mut elapsed = 0u64;
+    let mut unlocks: std::collections::HashMap<usize, u64> = Default::default();
+
+    for step in 0..60 {
+        let who = rng.below(accounts.len() as u64) as usize;
+        let account = accounts[who].clone();
+        match rng.below(3) {
+            0 => {
+                let asset = assets[rng.below(2) as usize];
+                let amount = 100 + rng.below(10_000) as u128;
+                let lock = 65_000 + 10_000 * rng.below(10);
+                let result = session.call_with_address(
+                    contract_address.clone(),
+                    "deposit_with_asset",
+                    &[
+                        AssetId(asset).encode(),
+                        amount.encode(),
+                        lock.encode(),
+                        (2000u32).encode(),
+                    ],
+                    NO_ENDOWMENT,
+                    account.clone(),
+                );
+                // A second deposit for the same account must be refused
+                assert_eq!(result.is_ok(), !unlocks.contains_key(&who), "seed {} step {}: deposit", seed, step);
+                if result.is_ok() {
+                    unlocks.insert(who, elapsed + lock);
+                }
+            }
+            1 => {
+                let result = session.call_with_address(
+                    contract_address.clone(),
+                    "claim_cross_chain",
+                    &[NO_OVERRIDE.encode()],
+                    NO_ENDOWMENT,
+                    account.clone(),
+                );
+                let unlocked = unlocks.get(&who).is_some_and(|unlock| elapsed >= *unlock);
+                assert_eq!(result.is_ok(), unlocked, "seed {} step {}: claim", seed, step);
+                if result.is_ok() {
+                    unlocks.remove(&who);
+                }
+            }
+            _ => {
+                let jump = 10_000 * (1 + rng.below(5));
+                session.advance_time(jump);
+                elapsed += jump;
+            }
+        }
+
+        assert_total_locked_invariant(&mut session, contract_address.clone(), &accounts)?;
+
+        // Per-asset totals add up to the same deposits
+        let mut expected = [0u128; 2];
+        for (index, account) in accounts.iter().enumerate() {
+            let info = get_deposit_info(&mut session, contract_address.clone(), account)?;
+            assert_eq!(info.is_some(), unlocks.contains_key(&index), "seed {} step {}: deposit set", seed, step);
+            if let Some(info) = info {
+                let slot = assets.iter().position(|asset| AssetId(*asset) == info.asset_id).ok_or("unknown asset")?;
+                expected[slot] += info.amount + info.reserved_fee;
+            }
+        }
+        for (slot, asset) in assets.iter().enumerate() {
+            session.call_and(contract_address.clone(), "get_locked_by_asset", &[AssetId(*asset).encode()], NO_ENDOWMENT)?;
+            let locked = session.last_call_return::<u128>().ok_or("no return value")??;
+            assert_eq!(locked, expected[slot], "seed {} step {}: locked_by_asset({})", seed, step, asset);
+        }
+    }
+
+    Ok(())
+}
+
+fn effective_status(
+    session: &mut Session,
+    contract_address: AccountId32,
+    account: &AccountId32,
+) -> Result<EffectiveStatus, Box<dyn std::error::Error>> {
+    session.call_and(contract_address, "effective_status", &[account.encode()], NO_ENDOWMENT)?;
+    Ok(session.last_call_return::<EffectiveStatus>().ok_or("no return value")??)
+}
+
+#[drink::test]
+fn test_effective_status_reports_each_blocker(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    assert_eq!(effective_status(&mut session, contract_address.clone(), &bob)?, EffectiveStatus::NoDeposit);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    let info = get_deposit_info(&mut session, contract_address.clone(), &alice)?.ok_or("missing deposit")?;
+    assert_eq!(
+        effective_status(&mut session, contract_address.clone(), &alice)?,
+        EffectiveStatus::Locked { unlock: info.unlock_timestamp }
+    );
+
+    // Matured, but compliance requires a registered destination
+    session.advance_time(121_000);
+    session.call_with_address(
+        contract_address.clone(),
+        "set_claim_destination_policy",
+        &[true.encode(), false.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    assert_eq!(
+        effective_status(&mut session, contract_address.clone(), &alice)?,
+        EffectiveStatus::DestinationNotRegistered
+    );
+
+    session.call_with_address(
+        contract_address.clone(),
+        "register_claim_destination",
+        &[(2000u32).encode(), info.destination_account.encode()],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+
+    // Strict fee assets with nothing configured for the destination
+    session.call_with_address(
+        contract_address.clone(),
+        "set_strict_fee_assets",
+        &[true.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    assert_eq!(
+        effective_status(&mut session, contract_address.clone(), &alice)?,
+        EffectiveStatus::Blocked { reason: VestingError::FeeAssetNotConfigured }
+    );
+    session.call_with_address(
+        contract_address.clone(),
+        "set_strict_fee_assets",
+        &[false.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    assert!(matches!(
+        effective_status(&mut session, contract_address.clone(), &alice)?,
+        EffectiveStatus::Claimable { emergency: false, .. }
+    ));
+
+    session.call_with_address(contract_address.clone(), "pause_claims", &[], NO_ENDOWMENT, admin.clone())?;
+    assert_eq!(
+        effective_status(&mut session, contract_address.clone(), &alice)?,
+        EffectiveStatus::ClaimsPaused
+    );
+    // The claim path agrees
+    let paused_claim = session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+    assert!(paused_claim.is_err());
+    session.call_with_address(contract_address.clone(), "unpause_claims", &[], NO_ENDOWMENT, admin.clone())?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "export_and_mark",
+        &[alice.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    assert_eq!(effective_status(&mut session, contract_address, &alice)?, EffectiveStatus::Migrated);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_effective_status_in_emergency(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    session.call_with_address(contract_address.clone(), "emergency_unlock", &[], NO_ENDOWMENT, admin)?;
+
+    // Still inside the lock, claimable only because of the emergency
+    session.call_and(contract_address.clone(), "preview_claim", &[alice.encode()], NO_ENDOWMENT)?;
+    let net = session
+        .last_call_return::<Result<vesting_vault::ClaimPreview, VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?
+        .net;
+    assert_eq!(
+        effective_status(&mut session, contract_address, &alice)?,
+        EffectiveStatus::Claimable { amount: net, emergency: true }
+    );
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_destination_sovereign_account_matches_reference(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Sibling sovereign accounts as derived by xcm-builder's SiblingParachainConvertsVia
+    let reference: [(u32, &[u8; 8]); 2] = [(1000, b"sibl\xe8\x03\0\0"), (2034, b"sibl\xf2\x07\0\0")];
+    for (parachain, prefix) in reference {
+        let mut expected = [0u8; 32];
+        expected[..8].copy_from_slice(prefix);
+        session.call_and(
+            contract_address.clone(),
+            "destination_sovereign_account",
+            &[parachain.encode()],
+            NO_ENDOWMENT,
+        )?;
+        let account = session.last_call_return::<[u8; 32]>().ok_or("no return value")??;
+        assert_eq!(account, expected, "parachain {}", parachain);
+    }
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_claim_destination_override(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    for parachain in [2000u32, 3000u32] {
+        session.call_with_address(
+            contract_address.clone(),
+            "add_allowed_destination",
+            &[parachain.encode()],
+            NO_ENDOWMENT,
+            admin.clone(),
+        )?;
+    }
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    session.advance_time(121_000);
+
+    // Overrides are held to the same allowlist as deposits
+    let disallowed = session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[Some((4000u32, [9u8; 32])).encode()],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+    assert!(disallowed.is_err(), "Claim to a destination outside the allowlist should fail");
+
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[Some((3000u32, [9u8; 32])).encode()],
+        NO_ENDOWMENT,
+        alice,
+    )?;
+    let receipt_id = session
+        .last_call_return::<Result<u64, VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?;
+
+    session.call_and(contract_address, "get_claim_receipt", &[receipt_id.encode()], NO_ENDOWMENT)?;
+    let receipt = session
+        .last_call_return::<Option<vesting_vault::ClaimReceipt>>()
+        .ok_or("no return value")??
+        .ok_or("missing receipt")?;
+    assert_eq!(receipt.destination_parachain, 3000);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_admin_set_destination_requires_allowlist(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+
+    // An empty allowlist lets deposits go anywhere, but not an admin override
+    let unlisted = session.call_with_address(
+        contract_address.clone(),
+        "admin_set_destination",
+        &[alice.encode(), (3000u32).encode(), [9u8; 32].encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    );
+    assert_eq!(reverted_with(unlisted)?, VestingError::DestinationNotAllowed);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "add_allowed_destination",
+        &[(3000u32).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address,
+        "admin_set_destination",
+        &[alice.encode(), (3000u32).encode(), [9u8; 32].encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_local_payout_keeps_asset_min_balance(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    for (account, amount) in [(alice.clone(), 1000u128), (bob.clone(), 500u128)] {
+        session.call_with_address(
+            contract_address.clone(),
+            "deposit_with_asset",
+            &[
+                AssetId(1).encode(),
+                amount.encode(),
+                (120_000u64).encode(),
+                (2000u32).encode(),
+            ],
+            NO_ENDOWMENT,
+            account,
+        )?;
+    }
+    session.call_with_address(
+        contract_address.clone(),
+        "set_asset_min_balance",
+        &[AssetId(1).encode(), (500u128).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "set_emergency_local_payout",
+        &[true.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(contract_address.clone(), "emergency_unlock", &[], NO_ENDOWMENT, admin.clone())?;
+
+    // 1500 held, 1000 paid: exactly the minimum stays behind
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        alice,
+    )?;
+
+    // Paying bob would empty the account
+    let reaping_claim = session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        bob.clone(),
+    );
+    assert!(reaping_claim.is_err(), "Payout below the asset's min balance should fail");
+    assert_eq!(get_total_locked(&mut session, contract_address.clone())?, 500);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_asset_min_balance",
+        &[AssetId(1).encode(), (0u128).encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        bob,
+    )?;
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 0);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_rounding_mode_applies_to_deposit_fee(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // 1 bps of 12_000 is 1.2 and of 18_000 is 1.8
+    session.call_with_address(
+        contract_address.clone(),
+        "set_deposit_fee_bps",
+        &[(1u16).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    let cases = [
+        (RoundingMode::Down, 18_000u128, 1u128),
+        (RoundingMode::Up, 12_000, 2),
+        (RoundingMode::Nearest, 12_000, 1),
+        (RoundingMode::Nearest, 18_000, 2),
+    ];
+    let mut treasury = 0u128;
+    for (i, (mode, amount, expected_fee)) in cases.into_iter().enumerate() {
+        session.call_with_address(
+            contract_address.clone(),
+            "set_rounding_mode",
+            &[mode.encode()],
+            NO_ENDOWMENT,
+            admin.clone(),
+        )?;
+        let depositor = AccountId32::new([10 + i as u8; 32]);
+        session.call_with_address(
+            contract_address.clone(),
+            "deposit_with_asset",
+            &[
+                AssetId(1).encode(),
+                amount.encode(),
+                (120_000u64).encode(),
+                (2000u32).encode(),
+            ],
+            NO_ENDOWMENT,
+            depositor.clone(),
+        )?;
+
+        session.call_and(contract_address.clone(), "get_treasury_balance", &[], NO_ENDOWMENT)?;
+        let balance = session.last_call_return::<u128>().ok_or("no return value")??;
+        assert_eq!(balance - treasury, expected_fee, "{:?} on {}", mode, amount);
+        treasury = balance;
+
+        let info = get_deposit_info(&mut session, contract_address.clone(), &depositor)?.ok_or("missing deposit")?;
+        assert_eq!(info.amount, amount - expected_fee);
+    }
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_update_config_is_all_or_nothing(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // A max_lock below the current min_lock (60_000) sinks the valid fee change too
+    let invalid = ConfigPatch {
+        max_lock: Some(30_000),
+        claim_fee_bps: Some(100),
+        ..Default::default()
+    };
+    let rejected = session.call_with_address(
+        contract_address.clone(),
+        "update_config",
+        &[invalid.encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    );
+    assert!(rejected.is_err(), "Patch leaving min_lock > max_lock should fail");
+
+    session.call_and(contract_address.clone(), "get_config", &[], NO_ENDOWMENT)?;
+    let config = session.last_call_return::<VaultConfig>().ok_or("no return value")??;
+    assert_eq!((config.max_lock, config.claim_fee_bps), (u64::MAX, 0));
+
+    // Lowering both bounds together is fine
+    let valid = ConfigPatch {
+        min_lock: Some(10_000),
+        max_lock: Some(30_000),
+        claim_fee_bps: Some(100),
+        ..Default::default()
+    };
+    session.call_with_address(
+        contract_address.clone(),
+        "update_config",
+        &[valid.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    session.call_and(contract_address, "get_config", &[], NO_ENDOWMENT)?;
+    let config = session.last_call_return::<VaultConfig>().ok_or("no return value")??;
+    assert_eq!((config.min_lock, config.max_lock, config.claim_fee_bps), (10_000, 30_000, 100));
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_raised_min_lock_waits_for_grace_window(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_min_lock_grace",
+        &[(10_000u64).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "set_lock_bounds",
+        &[(90_000u64).encode(), u64::MAX.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    session.call_and(contract_address.clone(), "get_min_lock_schedule", &[], NO_ENDOWMENT)?;
+    let (current, pending) = session
+        .last_call_return::<(u64, Option<(u64, u64)>)>()
+        .ok_or("no return value")??;
+    assert_eq!(current, 60_000);
+    assert_eq!(pending.map(|(min_lock, _)| min_lock), Some(90_000));
+
+    // Still within the grace window: the old minimum applies
+    let deposit = |session: &mut Session, account: AccountId32| {
+        session.call_with_address(
+            contract_address.clone(),
+            "deposit_with_asset",
+            &[
+                AssetId(1).encode(),
+                (1_000u128).encode(),
+                (70_000u64).encode(),
+                (2000u32).encode(),
+            ],
+            NO_ENDOWMENT,
+            account,
+        )
+    };
+    deposit(&mut session, alice)?;
+
+    session.advance_time(10_001);
+    assert!(deposit(&mut session, bob).is_err(), "Lock below the activated min_lock should fail");
+
+    session.call_and(contract_address, "get_min_lock_schedule", &[], NO_ENDOWMENT)?;
+    let schedule = session
+        .last_call_return::<(u64, Option<(u64, u64)>)>()
+        .ok_or("no return value")??;
+    assert_eq!(schedule, (90_000, None));
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_emergency_unlock_needs_guardian_approvals(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let guardians = [AccountId32::new([5u8; 32]), AccountId32::new([6u8; 32])];
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    for guardian in &guardians {
+        session.call_with_address(
+            contract_address.clone(),
+            "add_guardian",
+            &[guardian.encode()],
+            NO_ENDOWMENT,
+            admin.clone(),
+        )?;
+    }
+    session.call_with_address(
+        contract_address.clone(),
+        "set_threshold",
+        &[(2u32).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    // One approval is not enough for a threshold of two
+    session.call_with_address(
+        contract_address.clone(),
+        "approve_emergency",
+        &[],
+        NO_ENDOWMENT,
+        guardians[0].clone(),
+    )?;
+    let early = session.call_with_address(
+        contract_address.clone(),
+        "emergency_unlock",
+        &[],
+        NO_ENDOWMENT,
+        admin.clone(),
+    );
+    assert_eq!(reverted_with(early)?, VestingError::NotEnoughApprovals);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "approve_emergency",
+        &[],
+        NO_ENDOWMENT,
+        guardians[1].clone(),
+    )?;
+    session.call_with_address(contract_address.clone(), "emergency_unlock", &[], NO_ENDOWMENT, admin)?;
+
+    // Approvals are spent by the unlock
+    session.call_and(contract_address, "get_emergency_approvals", &[], NO_ENDOWMENT)?;
+    let approvals = session
+        .last_call_return::<Vec<AccountId32>>()
+        .ok_or("no return value")??;
+    assert!(approvals.is_empty());
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_fee_credit_keeps_its_asset(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "prefund_destination_fee",
+        &[AssetId(2).encode(), (500u128).encode(), (2000u32).encode()],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+
+    // A second asset can't be mixed into the same credit
+    let mixed = session.call_with_address(
+        contract_address.clone(),
+        "prefund_destination_fee",
+        &[AssetId(1).encode(), (500u128).encode(), (2000u32).encode()],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+    assert_eq!(reverted_with(mixed)?, VestingError::FeeAssetMismatch);
+
+    session.call_and(
+        contract_address,
+        "get_fee_credit",
+        &[alice.encode(), (2000u32).encode()],
+        NO_ENDOWMENT,
+    )?;
+    let credit = session
+        .last_call_return::<Option<(AssetId, u128)>>()
+        .ok_or("no return value")??;
+    assert_eq!(credit, Some((AssetId(2), 500)));
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_sweep_leaves_pending_large_deposit(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Token that accepts transfers and reports the vault holding 5000
+    let token = ContractMock::new()
+        .with_message(
+            [0x54, 0xb3, 0xc7, 0x6e],
+            mock_message(|_: (AccountId32, AccountId32, u128, Vec<u8>)| Ok::<(), u8>(())),
+        )
+        .with_message(
+            [0x65, 0x68, 0x38, 0x2f],
+            mock_message(|_: AccountId32| 5_000u128),
+        );
+    let token_address = session.mocking_api().deploy(token);
+    session.call_with_address(
+        contract_address.clone(),
+        "set_asset_contract",
+        &[AssetId(2).encode(), Some(token_address).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "set_large_deposit_threshold",
+        &[Some(1_000u128).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    // 3000 of the vault's balance is alice's unconfirmed escrow
+    session.call_with_address(
+        contract_address.clone(),
+        "initiate_large_deposit",
+        &[
+            AssetId(2).encode(),
+            (3_000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "remove_supported_asset",
+        &[AssetId(2).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "sweep_asset_dust",
+        &[AssetId(2).encode(), admin.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    let swept = session
+        .last_call_return::<Result<u128, VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?;
+    assert_eq!(swept, 2_000);
+
+    // The escrow is still there to refund
+    session.call_with_address(contract_address, "cancel_deposit", &[], NO_ENDOWMENT, alice)?;
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_total_claimable_matches_claim_quote(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_claim_fee_bps",
+        &[(100u16).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (10_000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    session.advance_time(121_000);
+
+    let EffectiveStatus::Claimable { amount, .. } =
+        effective_status(&mut session, contract_address.clone(), &alice)?
+    else {
+        return Err("deposit should be claimable".into());
+    };
+    session.call_and(contract_address.clone(), "total_claimable", &[alice.encode()], NO_ENDOWMENT)?;
+    let total = session.last_call_return::<u128>().ok_or("no return value")??;
+    assert_eq!(total, amount);
+
+    // Nothing is claimable while claims are paused
+    session.call_with_address(contract_address.clone(), "pause_claims", &[], NO_ENDOWMENT, admin)?;
+    session.call_and(contract_address, "total_claimable", &[alice.encode()], NO_ENDOWMENT)?;
+    assert_eq!(session.last_call_return::<u128>().ok_or("no return value")??, 0);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_schedule_follows_deposit_rules(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "set_deposit_fee_bps",
+        &[(100u16).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "set_large_deposit_threshold",
+        &[Some(5_000u128).encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+
+    // A small deposit only to learn the block time: its unlock is now + 120_000
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1_000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        bob.clone(),
+    )?;
+    session.call_and(contract_address.clone(), "get_treasury_balance", &[], NO_ENDOWMENT)?;
+    let treasury_before = session.last_call_return::<u128>().ok_or("no return value")??;
+    let unlock = get_deposit_info(&mut session, contract_address.clone(), &bob)?
+        .ok_or("missing deposit")?
+        .unlock_timestamp;
+
+    // A schedule above the large deposit threshold would skip confirmation
+    let large = session.call_with_address(
+        contract_address.clone(),
+        "deposit_schedule",
+        &[
+            AssetId(1).encode(),
+            (10_000u128).encode(),
+            vec![(10_000u128, unlock)].encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    );
+    assert_eq!(reverted_with(large)?, VestingError::ConfirmationRequired);
+
+    // A smaller one pays the deposit fee like any deposit
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_schedule",
+        &[
+            AssetId(1).encode(),
+            (4_000u128).encode(),
+            vec![(4_000u128, unlock)].encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice,
+    )?;
+    session.call_and(contract_address, "get_treasury_balance", &[], NO_ENDOWMENT)?;
+    let treasury = session.last_call_return::<u128>().ok_or("no return value")??;
+    assert_eq!(treasury - treasury_before, 40);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_claim_all_matured_sends_one_transfer_per_destination(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (10_000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    let unlock = get_deposit_info(&mut session, contract_address.clone(), &alice)?
+        .ok_or("missing deposit")?
+        .unlock_timestamp;
+
+    // Two tranches in the same asset to the same chain: one matures with the deposit
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_schedule",
+        &[
+            AssetId(1).encode(),
+            (4_000u128).encode(),
+            vec![(1_000u128, unlock), (3_000u128, unlock + 500_000)].encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    session.advance_time(121_000);
+
+    session.call_with_address(contract_address.clone(), "claim_all_matured", &[], NO_ENDOWMENT, alice.clone())?;
+    let receipts = session
+        .last_call_return::<Result<Vec<u64>, VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?;
+    assert_eq!(receipts.len(), 1);
+
+    // The deposit and the matured tranche are gone, the later tranche stays locked
+    assert!(get_deposit_info(&mut session, contract_address.clone(), &alice)?.is_none());
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 3_000);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_tvl_warning_fires_for_small_cap(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // A cap below MAX_BPS, where 90% of it is still a real amount
+    session.call_with_address(
+        contract_address.clone(),
+        "set_tvl_cap",
+        &[Some(1_000u128).encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (950u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice,
+    )?;
+    let warning = (950u128, 1_000u128).encode();
+    let fired = session
+        .record()
+        .last_event_batch()
+        .contract_events()
+        .iter()
+        .any(|data| *data == &warning[..]);
+    assert!(fired, "crossing 90% of a small cap should emit TvlCapNearlyReached");
+    assert_eq!(get_total_locked(&mut session, contract_address)?, 950);
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_locked_by_bucket_includes_schedule_tranches(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+    let bob = AccountId32::new([3u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(1).encode(),
+            (1_000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    let unlock = get_deposit_info(&mut session, contract_address.clone(), &alice)?
+        .ok_or("missing deposit")?
+        .unlock_timestamp;
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_schedule",
+        &[
+            AssetId(1).encode(),
+            (4_000u128).encode(),
+            vec![(1_000u128, unlock + 1_000_000), (3_000u128, unlock + 5_000_000)].encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        bob,
+    )?;
+
+    session.call_and(contract_address, "locked_by_bucket", &[(1_000_000u64).encode()], NO_ENDOWMENT)?;
+    let buckets = session
+        .last_call_return::<Result<Vec<(u64, u128)>, VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?;
+    let total: u128 = buckets.iter().map(|(_, locked)| locked).sum();
+    assert_eq!(total, 5_000);
+    // The later tranche sits alone in the last bucket
+    assert_eq!(buckets.last().map(|(_, locked)| *locked), Some(3_000));
+
+    Ok(())
+}
+
+#[drink::test]
+fn test_sweep_leaves_burned_fees(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    let admin = AccountId32::new([1u8; 32]);
+    let alice = AccountId32::new([2u8; 32]);
+
+    // Deploy the contract
+    let contract_address = session.deploy_bundle_and(
+        BundleProvider::local(),
+        "new",
+        &[admin.to_string()],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    // Token that accepts transfers and reports the vault holding 5000
+    let token = ContractMock::new()
+        .with_message(
+            [0x54, 0xb3, 0xc7, 0x6e],
+            mock_message(|_: (AccountId32, AccountId32, u128, Vec<u8>)| Ok::<(), u8>(())),
+        )
+        .with_message(
+            [0x65, 0x68, 0x38, 0x2f],
+            mock_message(|_: AccountId32| 5_000u128),
+        );
+    let token_address = session.mocking_api().deploy(token);
+    session.call_with_address(
+        contract_address.clone(),
+        "set_asset_contract",
+        &[AssetId(2).encode(), Some(token_address).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    // A 10% claim fee, half of it burned
+    session.call_with_address(
+        contract_address.clone(),
+        "set_claim_fee_bps",
+        &[(1_000u16).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address.clone(),
+        "set_burn_bps",
+        &[(5_000u16).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+
+    session.call_with_address(
+        contract_address.clone(),
+        "deposit_with_asset",
+        &[
+            AssetId(2).encode(),
+            (4_000u128).encode(),
+            (120_000u64).encode(),
+            (2000u32).encode(),
+        ],
+        NO_ENDOWMENT,
+        alice.clone(),
+    )?;
+    session.advance_time(121_000);
+    session.call_with_address(
+        contract_address.clone(),
+        "claim_cross_chain",
+        &[NO_OVERRIDE.encode()],
+        NO_ENDOWMENT,
+        alice,
+    )?;
+    session.call_and(contract_address.clone(), "get_burned", &[AssetId(2).encode()], NO_ENDOWMENT)?;
+    assert_eq!(session.last_call_return::<u128>().ok_or("no return value")??, 200);
+
+    session.call_with_address(
+        contract_address.clone(),
+        "remove_supported_asset",
+        &[AssetId(2).encode()],
+        NO_ENDOWMENT,
+        admin.clone(),
+    )?;
+    session.call_with_address(
+        contract_address,
+        "sweep_asset_dust",
+        &[AssetId(2).encode(), admin.encode()],
+        NO_ENDOWMENT,
+        admin,
+    )?;
+    let swept = session
+        .last_call_return::<Result<u128, VestingError>>()
+        .ok_or("no return value")??
+        .map_err(|err| format!("{:?}", err))?;
+    // The treasury's 200 and the 200 burned both stay behind
+    assert_eq!(swept, 4_600);
+
+    Ok(())
+}